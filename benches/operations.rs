@@ -87,6 +87,146 @@ fn bench_operations(c: &mut Criterion) {
             b.iter(|| Chunk::from_iter(0..N));
         })
         .bench_function("Vec", |b| b.iter(|| Vec::from_iter(0..N)));
+
+    // Benchmark balanced concatenation against plain concatenation
+    c.benchmark_group("balanced")
+        .bench_function("chunk_prepend", |b| {
+            b.iter(|| {
+                let mut chunk = Chunk::default();
+                for i in 0..N {
+                    chunk = Chunk::new(i).concat(chunk);
+                }
+                black_box(chunk);
+            })
+        })
+        .bench_function("chunk_prepend_balanced", |b| {
+            b.iter(|| {
+                let mut chunk = Chunk::default();
+                for i in 0..N {
+                    chunk = Chunk::new(i).concat_balanced(chunk);
+                }
+                black_box(chunk);
+            })
+        })
+        .bench_function("chunk_concat_fold", |b| {
+            b.iter(|| {
+                let chunk = (0..N)
+                    .map(|i| Chunk::new(i).transform(|x| x + 1))
+                    .fold(Chunk::default(), Chunk::concat);
+                black_box(chunk);
+            })
+        })
+        .bench_function("chunk_concat_fold_balanced", |b| {
+            b.iter(|| {
+                let chunk = (0..N)
+                    .map(|i| Chunk::new(i).transform(|x| x + 1))
+                    .fold(Chunk::default(), Chunk::concat_balanced);
+                black_box(chunk);
+            })
+        })
+        .bench_function("chunk_as_vec", |b| {
+            let chunk = (0..N).fold(Chunk::default(), |chunk, i| Chunk::new(i).concat(chunk));
+            b.iter(|| black_box(chunk.as_vec()))
+        })
+        .bench_function("chunk_as_vec_balanced", |b| {
+            let chunk = (0..N).fold(Chunk::default(), |chunk, i| {
+                Chunk::new(i).concat_balanced(chunk)
+            });
+            b.iter(|| black_box(chunk.as_vec()))
+        });
+
+    // Benchmark the lazy filtering nodes against their `transform_flatten` equivalents
+    let chunk: Chunk<_> = (0..N).collect();
+    c.benchmark_group("filter")
+        .bench_function("chunk_filter", |b| {
+            b.iter(|| black_box(chunk.clone().filter(|x| x % 2 == 0).as_vec()))
+        })
+        .bench_function("chunk_filter_transform_flatten", |b| {
+            b.iter(|| {
+                let filtered = chunk.clone().transform_flatten(|x| {
+                    if x % 2 == 0 {
+                        Chunk::new(x)
+                    } else {
+                        Chunk::default()
+                    }
+                });
+                black_box(filtered.as_vec())
+            })
+        })
+        .bench_function("chunk_take_while", |b| {
+            b.iter(|| black_box(chunk.clone().take_while(|x| *x < N / 2).iter().count()))
+        })
+        .bench_function("chunk_take_while_transform_flatten", |b| {
+            b.iter(|| {
+                // Stops at the first element that doesn't match, like `take_while` does
+                let flattened = chunk.clone().transform_flatten(Chunk::new);
+                black_box(flattened.iter().take_while(|x| **x < N / 2).count())
+            })
+        });
+
+    // Benchmark a chain of fused transforms against the equivalent unfused chain
+    c.benchmark_group("transform_chain")
+        .bench_function("chunk_transform", |b| {
+            b.iter(|| {
+                let chained =
+                    (0..10).fold(chunk.clone(), |chunk, i| chunk.transform(move |x| x + i));
+                black_box(chained.as_vec())
+            })
+        })
+        .bench_function("chunk_transform_flatten", |b| {
+            b.iter(|| {
+                let chained = (0..10).fold(chunk.clone(), |chunk, i| {
+                    chunk.transform_flatten(move |x| Chunk::new(x + i))
+                });
+                black_box(chained.as_vec())
+            })
+        })
+        .bench_function("chunk_transform_iter", |b| {
+            b.iter(|| {
+                let chained =
+                    (0..10).fold(chunk.clone(), |chunk, i| chunk.transform(move |x| x + i));
+                black_box(chained.iter().count())
+            })
+        })
+        .bench_function("chunk_transform_flatten_iter", |b| {
+            b.iter(|| {
+                let chained = (0..10).fold(chunk.clone(), |chunk, i| {
+                    chunk.transform_flatten(move |x| Chunk::new(x + i))
+                });
+                black_box(chained.iter().count())
+            })
+        });
+
+    // Benchmark using a chunk as a work queue against a `VecDeque`
+    c.benchmark_group("queue")
+        .bench_function("chunk_pop_front", |b| {
+            b.iter(|| {
+                let mut queue = Chunk::default();
+                for i in 0..N {
+                    queue = queue.append(i);
+                    if i % 2 == 1 {
+                        black_box(queue.pop_front());
+                    }
+                }
+                while let Some(a) = queue.pop_front() {
+                    black_box(a);
+                }
+            })
+        })
+        .bench_function("vec_deque_pop_front", |b| {
+            b.iter(|| {
+                let mut queue = std::collections::VecDeque::new();
+                for i in 0..N {
+                    queue.push_back(i);
+                    if i % 2 == 1 {
+                        black_box(queue.pop_front());
+                    }
+                }
+                while let Some(a) = queue.pop_front() {
+                    black_box(a);
+                }
+            })
+        });
 }
 
 criterion_group!(benches, bench_operations);
@@ -0,0 +1,225 @@
+//! Errors annotated with the path at which they occurred.
+//!
+//! A [`Cause`] pairs an error with a trace: the path segments leading to the place it was
+//! found, outermost first. Since the trace is a [`Chunk`], adding the context of an enclosing
+//! scope is an O(1) [`prepend`](Chunk::prepend), and it can be added to every error of a
+//! `Chunk<Cause<E>>` lazily.
+
+use alloc::string::String;
+use core::fmt;
+
+use crate::{Chunk, Validation};
+
+/// An error along with the path at which it occurred.
+///
+/// # Examples
+/// ```
+/// use tailcall_chunk::Cause;
+///
+/// let cause = Cause::new("expected a string")
+///     .with_context("name")
+///     .with_context("fields")
+///     .with_context("User")
+///     .with_context("types");
+/// assert_eq!(cause.path(), "types.User.fields.name");
+/// assert_eq!(cause.json_pointer(), "/types/User/fields/name");
+/// assert_eq!(cause.to_string(), "types.User.fields.name: expected a string");
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Cause<E> {
+    error: E,
+    trace: Chunk<String>,
+}
+
+impl<E> Cause<E> {
+    /// Creates a cause for an error that occurred at the root, with an empty trace.
+    pub fn new(error: E) -> Self {
+        Cause {
+            error,
+            trace: Chunk::default(),
+        }
+    }
+
+    /// Adds an enclosing path segment in front of the trace.
+    ///
+    /// Errors are usually found at the innermost level and bubble up, so each enclosing
+    /// scope adds its own segment on the way out.
+    ///
+    /// # Performance
+    /// O(1), as the trace is only prepended to.
+    pub fn with_context(self, segment: impl Into<String>) -> Self {
+        Cause {
+            error: self.error,
+            trace: self.trace.prepend(segment.into()),
+        }
+    }
+
+    /// Returns the error.
+    pub fn error(&self) -> &E {
+        &self.error
+    }
+
+    /// Returns the error, discarding the trace.
+    pub fn into_error(self) -> E {
+        self.error
+    }
+
+    /// Returns the path segments leading to the error, outermost first.
+    pub fn trace(&self) -> &Chunk<String> {
+        &self.trace
+    }
+
+    /// Renders the trace as a dotted path, such as `types.User.fields.name`.
+    ///
+    /// The path of an error at the root is empty.
+    pub fn path(&self) -> String {
+        let mut path = String::new();
+        for (i, segment) in self.trace.iter().enumerate() {
+            if i > 0 {
+                path.push('.');
+            }
+            path.push_str(&segment);
+        }
+        path
+    }
+
+    /// Renders the trace as a [JSON pointer](https://www.rfc-editor.org/rfc/rfc6901), such
+    /// as `/types/User/fields/name`.
+    ///
+    /// Segments containing `~` or `/` are escaped. The pointer of an error at the root is
+    /// empty.
+    ///
+    /// # Examples
+    /// ```
+    /// use tailcall_chunk::Cause;
+    ///
+    /// let cause = Cause::new(()).with_context("a/b").with_context("paths");
+    /// assert_eq!(cause.json_pointer(), "/paths/a~1b");
+    /// ```
+    pub fn json_pointer(&self) -> String {
+        let mut pointer = String::new();
+        for segment in self.trace.iter() {
+            pointer.push('/');
+            pointer.push_str(&segment.replace('~', "~0").replace('/', "~1"));
+        }
+        pointer
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for Cause<E> {
+    /// Formats the error, preceded by its dotted path unless it occurred at the root.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.trace.is_null() {
+            write!(f, "{}", self.error)
+        } else {
+            write!(f, "{}: {}", self.path(), self.error)
+        }
+    }
+}
+
+impl<E: Clone + 'static> Chunk<Cause<E>> {
+    /// Adds an enclosing path segment in front of the trace of every error in the chunk.
+    ///
+    /// Like [`transform`](Chunk::transform), this is lazy: the segment is only added when
+    /// the errors are read, so annotating a large chunk of errors at every level of a deep
+    /// structure costs O(1) per level.
+    ///
+    /// # Examples
+    /// ```
+    /// use tailcall_chunk::{Cause, Chunk};
+    ///
+    /// let errors = Chunk::default()
+    ///     .append(Cause::new("too long").with_context("name"))
+    ///     .append(Cause::new("missing").with_context("age"))
+    ///     .with_context("user");
+    /// let paths: Vec<_> = errors.iter().map(|cause| cause.path()).collect();
+    /// assert_eq!(paths, vec!["user.name", "user.age"]);
+    /// ```
+    pub fn with_context(self, segment: impl Into<String>) -> Self {
+        let segment = segment.into();
+        self.transform(move |cause| cause.with_context(segment.clone()))
+    }
+}
+
+impl<E: Clone + 'static, A> Validation<Cause<E>, A> {
+    /// Adds an enclosing path segment in front of the trace of every error, if the
+    /// validation failed.
+    ///
+    /// # Examples
+    /// ```
+    /// use tailcall_chunk::{Cause, Validation};
+    ///
+    /// let name: Validation<_, String> = Validation::fail(Cause::new("missing"));
+    /// let user = name.with_context("name").with_context("user");
+    /// let errors = user.into_result().unwrap_err();
+    /// assert_eq!(errors[0].to_string(), "user.name: missing");
+    /// ```
+    pub fn with_context(self, segment: impl Into<String>) -> Self {
+        match self {
+            Validation::Valid(a) => Validation::Valid(a),
+            Validation::Invalid(errors) => Validation::Invalid(errors.with_context(segment)),
+        }
+    }
+}
+
+impl<E> From<E> for Cause<E> {
+    /// Creates a cause for an error that occurred at the root.
+    fn from(error: E) -> Self {
+        Cause::new(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paths() {
+        let root = Cause::new("invalid");
+        assert_eq!(root.path(), "");
+        assert_eq!(root.json_pointer(), "");
+        assert_eq!(root.to_string(), "invalid");
+
+        let nested = root.with_context("0").with_context("items~");
+        assert_eq!(nested.path(), "items~.0");
+        assert_eq!(nested.json_pointer(), "/items~0/0");
+        assert_eq!(nested.trace().as_vec(), vec!["items~", "0"]);
+        assert_eq!(nested.into_error(), "invalid");
+    }
+
+    #[test]
+    fn test_chunk_with_context() {
+        let errors: Chunk<_> = (0..3)
+            .map(|i| Cause::new(i).with_context(i.to_string()))
+            .collect();
+        let annotated = errors.clone().with_context("list").with_context("root");
+
+        // The original errors are left untouched
+        assert_eq!(errors.as_vec()[1].path(), "1");
+        let paths: Vec<_> = annotated.iter().map(|cause| cause.path()).collect();
+        assert_eq!(paths, vec!["root.list.0", "root.list.1", "root.list.2"]);
+    }
+
+    #[test]
+    fn test_validation_with_context() {
+        let field = |name: &str, valid: bool| {
+            let validation = if valid {
+                Validation::Valid(())
+            } else {
+                Validation::fail(Cause::new("invalid"))
+            };
+            validation.with_context(name)
+        };
+        let user = field("name", false)
+            .and(field("age", true))
+            .and(field("email", false))
+            .with_context("User");
+        let messages: Vec<_> = user
+            .into_result()
+            .unwrap_err()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(messages, vec!["User.name: invalid", "User.email: invalid"]);
+    }
+}
@@ -20,7 +20,18 @@
 //! assert_eq!(combined.as_vec(), vec![1, 2, 3, 4]);
 //! ```
 
-use std::{cell::RefCell, rc::Rc};
+use alloc::{borrow::Cow, boxed::Box, rc::Rc, vec, vec::Vec};
+use core::{
+    any::Any,
+    cell::OnceCell,
+    fmt,
+    ops::{ControlFlow, Deref, Range},
+};
+
+use crate::{
+    sink::{Extender, TryForEach},
+    IntoIter, Iter, SharedElementError, Sink, TryIntoIter,
+};
 
 /// A persistent data structure that provides efficient append and concatenation operations.
 ///
@@ -35,10 +46,9 @@ use std::{cell::RefCell, rc::Rc};
 /// - Converting to Vec: O(n)
 ///
 /// # Implementation Details
-/// The data structure is implemented as an enum with three variants:
-/// - `Empty`: Represents an empty chunk
-/// - `Append`: Represents a single element appended to another chunk
-/// - `Concat`: Represents the concatenation of two chunks
+/// The data structure is a tree whose nodes are kept private, so that they can't be
+/// modified behind the back of the versions that share them. They can still be inspected,
+/// read-only, through [`view`](Chunk::view).
 ///
 /// # Examples
 /// ```
@@ -58,25 +68,171 @@ use std::{cell::RefCell, rc::Rc};
 /// - [Persistent Data Structures](https://en.wikipedia.org/wiki/Persistent_data_structure)
 /// - [Structural Sharing](https://hypirion.com/musings/understanding-persistent-vector-pt-1)
 #[derive(Clone)]
-pub enum Chunk<A> {
+pub struct Chunk<A> {
+    pub(crate) node: Node<A>,
+}
+
+/// The nodes a [`Chunk`] is built from.
+#[derive(Clone)]
+pub(crate) enum Node<A> {
     /// Represents an empty chunk with no elements
     Empty,
     /// Represents a chunk containing exactly one element
     Single(A),
     /// Represents the concatenation of two chunks, enabling O(1) concatenation
-    Concat(Rc<Chunk<A>>, Rc<Chunk<A>>),
+    Concat(Link<A>, Link<A>),
     /// Represents a collection of elements
-    Collect(Rc<RefCell<Vec<A>>>),
-    /// Represents a lazy transformation that flattens elements
-    TransformFlatten(Rc<Chunk<A>>, Rc<dyn Fn(A) -> Chunk<A>>),
+    Collect(Rc<Vec<A>>),
+    /// Represents a contiguous range of the elements of a collection
+    Slice(Rc<Vec<A>>, Range<usize>),
+    /// Represents a lazy operation on every element of a chunk
+    Transform(Link<A>, Op<A>),
+    /// Represents a lazy transformation of a chunk with another element type
+    Map(Mapped<A>),
+    /// Represents a chunk whose elements are cached the first time they are evaluated
+    Memo(Link<A>, Rc<dyn MemoCache<A>>),
+    /// Represents the elements of a chunk in reverse order
+    Reverse(Link<A>),
+}
+
+/// The cache of a `Memo` node, filled the first time its elements are evaluated.
+///
+/// Nodes only hold it as a trait object, so that its interior mutability doesn't show in the
+/// type of a [`Chunk`], which can be the key of a hashed collection.
+pub(crate) trait MemoCache<A> {
+    /// Returns the cached elements, if they have been evaluated.
+    fn get(&self) -> Option<&Rc<Vec<A>>>;
+
+    /// Caches `vec`, unless elements have been cached in the meantime, and returns the
+    /// cached elements.
+    fn set(&self, vec: Rc<Vec<A>>) -> &Rc<Vec<A>>;
+
+    /// Takes the cached elements out, leaving the cache empty.
+    fn take(&mut self) -> Option<Rc<Vec<A>>>;
+}
+
+impl<A> MemoCache<A> for OnceCell<Rc<Vec<A>>> {
+    fn get(&self) -> Option<&Rc<Vec<A>>> {
+        OnceCell::get(self)
+    }
+
+    fn set(&self, vec: Rc<Vec<A>>) -> &Rc<Vec<A>> {
+        self.get_or_init(|| vec)
+    }
+
+    fn take(&mut self) -> Option<Rc<Vec<A>>> {
+        OnceCell::take(self)
+    }
+}
+
+/// The functions of a fused `Map` operation, in the order they apply.
+pub(crate) type Functions<A> = Rc<Vec<Rc<dyn Fn(A) -> A>>>;
+
+/// An operation applied lazily to every element of a chunk, by a `Transform` node.
+pub(crate) enum Op<A> {
+    /// Replaces each element with the result of the functions, applied in order
+    Map(Functions<A>),
+    /// Replaces each element with the elements of a chunk
+    Flatten(Rc<dyn Fn(A) -> Chunk<A>>),
+    /// Replaces each element with at most one element
+    FilterMap(Rc<dyn Fn(A) -> Option<A>>),
+    /// Keeps the elements up to the first one that doesn't match
+    TakeWhile(Rc<dyn Fn(&A) -> bool>),
+    /// Drops the elements up to the first one that doesn't match
+    SkipWhile(Rc<dyn Fn(&A) -> bool>),
+}
+
+impl<A> Clone for Op<A> {
+    fn clone(&self) -> Self {
+        match self {
+            Op::Map(fs) => Op::Map(fs.clone()),
+            Op::Flatten(f) => Op::Flatten(f.clone()),
+            Op::FilterMap(f) => Op::FilterMap(f.clone()),
+            Op::TakeWhile(p) => Op::TakeWhile(p.clone()),
+            Op::SkipWhile(p) => Op::SkipWhile(p.clone()),
+        }
+    }
+}
+
+impl<A> Op<A> {
+    /// Runs `a` through the functions of a `Map` operation, in order.
+    pub(crate) fn map_all(fs: &[Rc<dyn Fn(A) -> A>], a: A) -> A {
+        fs.iter().fold(a, |a, f| f(a))
+    }
+
+    /// Returns `true` if what the operation does with an element depends on the elements
+    /// before it, so that it can't be applied to the elements in reverse order. Traversals
+    /// walking such a node backwards evaluate it forwards first.
+    pub(crate) fn is_order_dependent(&self) -> bool {
+        matches!(self, Op::TakeWhile(_) | Op::SkipWhile(_))
+    }
+}
+
+impl<A> Node<A> {
+    /// Copies the node without copying any element, which is possible for every kind of
+    /// node but `Single`.
+    fn share(&self) -> Option<Self> {
+        let node = match self {
+            Node::Empty => Node::Empty,
+            Node::Single(_) => return None,
+            Node::Concat(a, b) => Node::Concat(a.clone(), b.clone()),
+            Node::Collect(vec) => Node::Collect(vec.clone()),
+            Node::Slice(vec, range) => Node::Slice(vec.clone(), range.clone()),
+            Node::Transform(a, op) => Node::Transform(a.clone(), op.clone()),
+            Node::Map(mapped) => Node::Map(mapped.clone()),
+            Node::Memo(a, cache) => Node::Memo(a.clone(), cache.clone()),
+            Node::Reverse(a) => Node::Reverse(a.clone()),
+        };
+        Some(node)
+    }
+}
+
+impl<A> From<Node<A>> for Chunk<A> {
+    fn from(node: Node<A>) -> Self {
+        Chunk { node }
+    }
+}
+
+/// A read-only view of the root node of a [`Chunk`], returned by [`Chunk::view`].
+///
+/// It shows how a chunk is structured without giving access to the shared nodes
+/// themselves, so inspecting a chunk can never affect the other versions that share it.
+/// New kinds of nodes may be added in the future.
+#[non_exhaustive]
+pub enum ChunkNode<'a, A> {
+    /// A chunk with no elements
+    Empty,
+    /// A chunk containing exactly one element
+    Single(&'a A),
+    /// The concatenation of two chunks
+    Concat(&'a Chunk<A>, &'a Chunk<A>),
+    /// A collection of elements stored contiguously
+    Collect(&'a [A]),
+    /// A pending [`transform`](Chunk::transform), [`inspect`](Chunk::inspect) or
+    /// [`map`](Chunk::map) that keeps the element type, of the given chunk, which may stand
+    /// for several consecutive transforms
+    Transform(&'a Chunk<A>),
+    /// A pending [`transform_flatten`](Chunk::transform_flatten), or
+    /// [`flat_map`](Chunk::flat_map) that keeps the element type, of the given chunk
+    TransformFlatten(&'a Chunk<A>),
+    /// A pending [`filter`](Chunk::filter), [`filter_map`](Chunk::filter_map),
+    /// [`take_while`](Chunk::take_while) or [`skip_while`](Chunk::skip_while) of the given
+    /// chunk
+    Filter(&'a Chunk<A>),
+    /// A pending [`map`](Chunk::map) or [`flat_map`](Chunk::flat_map) of a chunk with
+    /// another element type, which can't be inspected
+    Map,
+    /// A [memoized](Chunk::memoize) chunk, along with its cached elements if it has
+    /// already been evaluated
+    Memo(&'a Chunk<A>, Option<&'a [A]>),
+    /// A [reversal](Chunk::reverse) of the given chunk
+    Reverse(&'a Chunk<A>),
 }
 
 impl<A> Default for Chunk<A> {
     /// Creates a new empty chunk.
-    ///
-    /// This is equivalent to using [`Chunk::Empty`].
     fn default() -> Self {
-        Chunk::Empty
+        Node::Empty.into()
     }
 }
 
@@ -94,7 +250,41 @@ impl<A> Chunk<A> {
     /// assert!(!chunk.is_null());
     /// ```
     pub fn new(a: A) -> Self {
-        Chunk::Single(a)
+        Node::Single(a).into()
+    }
+
+    /// Returns a read-only view of the root node of the chunk.
+    ///
+    /// This is meant for debugging and for tools that need to look at how a chunk is
+    /// structured. Nothing is evaluated, and the view borrows from the chunk.
+    ///
+    /// # Examples
+    /// ```
+    /// use tailcall_chunk::{Chunk, ChunkNode};
+    ///
+    /// let chunk = Chunk::new(1).concat(Chunk::new(2).transform(|x| x * 10));
+    /// match chunk.view() {
+    ///     ChunkNode::Concat(a, b) => {
+    ///         assert!(matches!(a.view(), ChunkNode::Single(1)));
+    ///         assert!(matches!(b.view(), ChunkNode::Transform(_)));
+    ///     }
+    ///     _ => unreachable!(),
+    /// }
+    /// ```
+    pub fn view(&self) -> ChunkNode<'_, A> {
+        match &self.node {
+            Node::Empty => ChunkNode::Empty,
+            Node::Single(a) => ChunkNode::Single(a),
+            Node::Concat(a, b) => ChunkNode::Concat(a, b),
+            Node::Collect(vec) => ChunkNode::Collect(vec),
+            Node::Slice(vec, range) => ChunkNode::Collect(&vec[range.clone()]),
+            Node::Transform(a, Op::Map(_)) => ChunkNode::Transform(a),
+            Node::Transform(a, Op::Flatten(_)) => ChunkNode::TransformFlatten(a),
+            Node::Transform(a, _) => ChunkNode::Filter(a),
+            Node::Map(_) => ChunkNode::Map,
+            Node::Memo(a, cell) => ChunkNode::Memo(a, cell.get().map(|vec| vec.as_slice())),
+            Node::Reverse(a) => ChunkNode::Reverse(a),
+        }
     }
 
     /// Returns `true` if the chunk is empty.
@@ -110,13 +300,106 @@ impl<A> Chunk<A> {
     /// assert!(!non_empty.is_null());
     /// ```
     pub fn is_null(&self) -> bool {
-        match self {
-            Chunk::Empty => true,
-            Chunk::Collect(vec) => vec.borrow().is_empty(),
+        self.size_hint().1 == Some(0)
+    }
+
+    /// Returns the number of elements in the chunk, if it is known without evaluation.
+    ///
+    /// Element counts are cached in the links of every `Concat` node, so this is O(1).
+    /// It returns `None` when the chunk contains a pending [`transform_flatten`](Chunk::transform_flatten),
+    /// since the number of elements it produces is only known once it is evaluated.
+    /// Use [`size_hint`](Chunk::size_hint) to get bounds in that case.
+    ///
+    /// # Examples
+    /// ```
+    /// use tailcall_chunk::Chunk;
+    ///
+    /// let chunk = Chunk::default().append(1).append(2).prepend(0);
+    /// assert_eq!(chunk.len(), Some(3));
+    ///
+    /// let flattened = chunk.transform_flatten(|x| Chunk::default().append(x).append(x));
+    /// assert_eq!(flattened.len(), None);
+    /// ```
+    pub fn len(&self) -> Option<usize> {
+        match self.size_hint() {
+            (lower, Some(upper)) if lower == upper => Some(lower),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if the chunk has no elements.
+    ///
+    /// Unlike [`is_null`](Chunk::is_null), this also gives an exact answer for chunks with
+    /// pending transformations, by lazily evaluating them up to their first element.
+    ///
+    /// # Examples
+    /// ```
+    /// use tailcall_chunk::Chunk;
+    ///
+    /// let filtered = Chunk::default().append(1).transform_flatten(|_| Chunk::default());
+    /// assert!(!filtered.is_null());
+    /// assert!(filtered.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool
+    where
+        A: Clone,
+    {
+        match self.size_hint() {
+            (_, Some(0)) => true,
+            (0, _) => self.iter().next().is_none(),
             _ => false,
         }
     }
 
+    /// Returns the bounds on the number of elements in the chunk.
+    ///
+    /// Like [`Iterator::size_hint`], this returns a lower bound and an optional upper bound.
+    /// Both are exact unless the chunk has pending transformations that change the number
+    /// of elements. A pending [`transform_flatten`](Chunk::transform_flatten) or
+    /// [`flat_map`](Chunk::flat_map) can produce any number of elements, so it contributes
+    /// `(0, None)`, while a pending [`filter`](Chunk::filter) only keeps its upper bound.
+    /// The bounds saturate instead of overflowing for chunks that share the same subtree
+    /// many times.
+    ///
+    /// This is O(1), and is used to preallocate buffers when a chunk is materialized.
+    ///
+    /// # Examples
+    /// ```
+    /// use tailcall_chunk::Chunk;
+    ///
+    /// let chunk: Chunk<_> = (0..10).collect();
+    /// assert_eq!(chunk.size_hint(), (10, Some(10)));
+    ///
+    /// let flattened = chunk.clone().transform_flatten(Chunk::new);
+    /// assert_eq!(flattened.concat(chunk).size_hint(), (10, None));
+    /// ```
+    pub fn size_hint(&self) -> (usize, Option<usize>) {
+        match &self.node {
+            Node::Empty => (0, Some(0)),
+            Node::Single(_) => (1, Some(1)),
+            Node::Collect(vec) => (vec.len(), Some(vec.len())),
+            Node::Slice(_, range) => (range.len(), Some(range.len())),
+            Node::Concat(a, b) => {
+                let (a_lower, a_upper) = a.size_hint();
+                let (b_lower, b_upper) = b.size_hint();
+                let upper = match (a_upper, b_upper) {
+                    (Some(a), Some(b)) => a.checked_add(b),
+                    _ => None,
+                };
+                (a_lower.saturating_add(b_lower), upper)
+            }
+            Node::Transform(a, Op::Map(_)) => a.size_hint(),
+            Node::Transform(_, Op::Flatten(_)) => (0, None),
+            Node::Transform(a, _) => (0, a.size_hint().1),
+            Node::Map(mapped) => mapped.0.size_hint(),
+            Node::Memo(a, cell) => match cell.get() {
+                Some(vec) => (vec.len(), Some(vec.len())),
+                None => a.size_hint(),
+            },
+            Node::Reverse(a) => a.size_hint(),
+        }
+    }
+
     /// Append a new element to the chunk.
     ///
     /// This operation has O(1) complexity as it creates a new `Append` variant
@@ -172,24 +455,132 @@ impl<A> Chunk<A> {
     /// assert_eq!(combined.as_vec(), vec![1, 2, 3, 4]);
     /// ```
     pub fn concat(self, other: Chunk<A>) -> Chunk<A> {
-        match (self, other) {
+        let node = match (self.node, other.node) {
             // Handle null cases
-            (Chunk::Empty, other) => other,
-            (this, Chunk::Empty) => this,
-            (Chunk::Single(a), Chunk::Single(b)) => {
-                Chunk::Collect(Rc::new(RefCell::new(vec![a, b])))
-            }
-            (Chunk::Collect(vec), Chunk::Single(a)) => {
-                if Rc::strong_count(&vec) == 1 {
-                    // Only clone if there are no other references
-                    vec.borrow_mut().push(a);
-                    Chunk::Collect(vec)
+            (Node::Empty, other) => other,
+            (this, Node::Empty) => this,
+            (Node::Single(a), Node::Single(b)) => Node::Collect(Rc::new(vec![a, b])),
+            (Node::Collect(mut vec), Node::Single(a)) => {
+                if let Some(inner) = Rc::get_mut(&mut vec) {
+                    // Push in place if there are no other references
+                    inner.push(a);
+                    Node::Collect(vec)
                 } else {
-                    Chunk::Concat(Rc::new(Chunk::Collect(vec)), Rc::new(Chunk::Single(a)))
+                    Node::Concat(
+                        Link::new(Node::Collect(vec).into()),
+                        Link::new(Node::Single(a).into()),
+                    )
                 }
             }
             // Handle all other cases with Concat
-            (this, that) => Chunk::Concat(Rc::new(this), Rc::new(that)),
+            (this, that) => Node::Concat(Link::new(this.into()), Link::new(that.into())),
+        };
+        node.into()
+    }
+
+    /// Concatenates this chunk with another chunk, rebalancing the result if needed.
+    ///
+    /// [`concat`](Chunk::concat) never restructures its arguments, so repeated `prepend`s
+    /// or a fold of `concat` over many small chunks build trees of linear depth. This
+    /// method behaves like `concat`, but whenever the depth of the result exceeds twice
+    /// the depth of a perfectly balanced tree, it is rebalanced like a rope: the already
+    /// balanced subtrees at the top are collected, in order, and a new weight-balanced
+    /// tree of at most 1.5 times the optimal depth is built over them. Elements are never
+    /// copied or evaluated.
+    ///
+    /// # Performance
+    /// - Depth of the result: O(log n) in the number of concatenated pieces
+    /// - Without a rebalance: O(1)
+    /// - With a rebalance: proportional to the number of nodes rebuilt, which is bounded
+    ///   by the number of concatenations since the previous rebalance, so repeated appends,
+    ///   prepends or folds cost amortized O(1)
+    ///
+    /// # Examples
+    /// ```
+    /// use tailcall_chunk::Chunk;
+    ///
+    /// let mut chunk = Chunk::default();
+    /// for i in (0..1000).rev() {
+    ///     chunk = Chunk::new(i).concat_balanced(chunk);
+    /// }
+    /// assert_eq!(chunk.as_vec(), (0..1000).collect::<Vec<_>>());
+    /// ```
+    pub fn concat_balanced(self, other: Chunk<A>) -> Chunk<A> {
+        let chunk = self.concat(other);
+        if Self::is_balanced(chunk.depth(), chunk.leaves()) {
+            chunk
+        } else {
+            chunk.balance()
+        }
+    }
+
+    /// Rebalances the chunk so that its depth is logarithmic in its number of pieces.
+    ///
+    /// See [`concat_balanced`](Chunk::concat_balanced) for the strategy. Subtrees that are
+    /// already balanced, and subtrees shared with other chunks, are reused as they are.
+    /// A chunk that is already balanced is returned unchanged.
+    ///
+    /// # Examples
+    /// ```
+    /// use tailcall_chunk::Chunk;
+    ///
+    /// let chunk = (0..100).fold(Chunk::default(), |chunk, i| chunk.prepend(i));
+    /// let balanced = chunk.clone().balance();
+    /// assert_eq!(balanced.as_vec(), chunk.as_vec());
+    /// ```
+    pub fn balance(self) -> Chunk<A> {
+        let leaves = self.leaves();
+        if Self::is_balanced(self.depth(), leaves) {
+            return self;
+        }
+        let (a, b) = match self.node {
+            Node::Concat(a, b) => (a, b),
+            node => return node.into(),
+        };
+
+        // Collect the balanced subtrees below the unbalanced top of the tree, in order, and
+        // rebuild well within the bound so that the next rebalance is some way off
+        let pieces = Self::open_pieces(vec![a, b], Self::is_balanced);
+        Self::concat_pieces(pieces, Self::balanced_depth(leaves) * 3 / 2 + 1)
+    }
+
+    /// Reverses the order of the elements of the chunk.
+    ///
+    /// This is lazy: the chunk is wrapped in a node that makes traversals walk `Concat`
+    /// nodes right to left and `Collect` nodes backwards, so no element is copied or
+    /// evaluated. Reversing a reversed chunk unwraps it again.
+    ///
+    /// # Performance
+    /// O(1). A traversal of the reversed chunk evaluates pending transformations one element
+    /// at a time from the end, including [`map`](Chunk::map) and
+    /// [`flat_map`](Chunk::flat_map), which walk their source chunk backwards. The exceptions
+    /// are:
+    /// - [`take_while`](Chunk::take_while) and [`skip_while`](Chunk::skip_while): since the
+    ///   elements they keep depend on the ones before, they are evaluated forwards into a
+    ///   temporary `Vec` first.
+    /// - Memoized nodes, which are evaluated whole to fill their cache, like in any traversal.
+    ///
+    /// # Examples
+    /// ```
+    /// use tailcall_chunk::Chunk;
+    ///
+    /// let log = Chunk::default().append("started").append("running").append("stopped");
+    /// let newest_first = log.clone().reverse();
+    /// assert_eq!(newest_first.as_vec(), vec!["stopped", "running", "started"]);
+    /// assert_eq!(newest_first.reverse().as_vec(), log.as_vec());
+    /// ```
+    pub fn reverse(self) -> Self {
+        match self.node {
+            node @ (Node::Empty | Node::Single(_)) => node.into(),
+            Node::Reverse(mut a) => match a.take_unique() {
+                Some(chunk) => chunk,
+                None => match a.node.share() {
+                    Some(node) => node.into(),
+                    // Only `Single` nodes can't be shared, and they are never reversed
+                    None => Node::Reverse(Link::new(Node::Reverse(a).into())).into(),
+                },
+            },
+            node => Node::Reverse(Link::new(node.into())).into(),
         }
     }
 
@@ -199,13 +590,17 @@ impl<A> Chunk<A> {
     /// performing it. The transformation is only executed when [`as_vec`](Chunk::as_vec)
     /// or [`as_vec_mut`](Chunk::as_vec_mut) is called.
     ///
+    /// Consecutive transforms are fused into a single node as they are built, so a chain
+    /// of transforms is evaluated in one pass over the elements, without intermediate
+    /// buffers, and keeps the number of elements known.
+    ///
     /// # Performance
     /// - Creating the transformation: O(1)
     /// - Executing the transformation (during [`as_vec`](Chunk::as_vec)): O(n)
     ///
     /// # Arguments
     /// * `f` - A function that takes a reference to an element of type `A` and returns
-    ///         a new element of type `A`
+    ///   a new element of type `A`
     ///
     /// # Examples
     /// ```
@@ -218,7 +613,7 @@ impl<A> Chunk<A> {
     /// assert_eq!(doubled.as_vec(), vec![2, 4, 6]);
     /// ```
     pub fn transform(self, f: impl Fn(A) -> A + 'static) -> Self {
-        self.transform_flatten(move |a| Chunk::new(f(a)))
+        self.push_map(Rc::new(f))
     }
 
     /// Materializes a chunk by converting it into a collected form.
@@ -249,7 +644,7 @@ impl<A> Chunk<A> {
     where
         A: Clone,
     {
-        Chunk::Collect(Rc::new(RefCell::new(self.as_vec())))
+        Node::Collect(Rc::new(self.into_vec())).into()
     }
 
     /// Transforms each element in the chunk into a new chunk and flattens the result.
@@ -264,7 +659,7 @@ impl<A> Chunk<A> {
     ///
     /// # Arguments
     /// * `f` - A function that takes an element of type `A` and returns
-    ///         a new `Chunk<A>`
+    ///   a new `Chunk<A>`
     ///
     /// # Examples
     /// ```
@@ -278,61 +673,1373 @@ impl<A> Chunk<A> {
     /// assert_eq!(expanded.as_vec(), vec![1, 2, 2, 3]);
     /// ```
     pub fn transform_flatten(self, f: impl Fn(A) -> Chunk<A> + 'static) -> Self {
-        Chunk::TransformFlatten(Rc::new(self), Rc::new(f))
+        self.apply(Op::Flatten(Rc::new(f)))
     }
 
-    /// Converts the chunk into a vector of references to its elements.
+    /// Keeps only the elements that match the predicate.
     ///
-    /// This operation has O(n) complexity where n is the number of elements
-    /// in the chunk.
+    /// Like [`transform`](Chunk::transform), this is lazy, but elements are filtered inline
+    /// while the chunk is traversed, without creating a chunk for every element as the
+    /// equivalent [`transform_flatten`](Chunk::transform_flatten) would.
     ///
     /// # Examples
     /// ```
     /// use tailcall_chunk::Chunk;
     ///
-    /// let chunk = Chunk::default().append(1).append(2).append(3);
-    /// assert_eq!(chunk.as_vec(), vec![1, 2, 3]);
+    /// let chunk: Chunk<_> = (1..=6).collect();
+    /// assert_eq!(chunk.filter(|x| x % 2 == 0).as_vec(), vec![2, 4, 6]);
     /// ```
-    pub fn as_vec(&self) -> Vec<A>
+    pub fn filter(self, p: impl Fn(&A) -> bool + 'static) -> Self {
+        self.filter_map(move |a| if p(&a) { Some(a) } else { None })
+    }
+
+    /// Transforms each element in the chunk, keeping only the results that are `Some`.
+    ///
+    /// This is lazy and evaluated inline, like [`filter`](Chunk::filter).
+    ///
+    /// # Examples
+    /// ```
+    /// use tailcall_chunk::Chunk;
+    ///
+    /// let chunk: Chunk<_> = (1..=4).collect();
+    /// let halves = chunk.filter_map(|x| if x % 2 == 0 { Some(x / 2) } else { None });
+    /// assert_eq!(halves.as_vec(), vec![1, 2]);
+    /// ```
+    pub fn filter_map(self, f: impl Fn(A) -> Option<A> + 'static) -> Self {
+        self.apply(Op::FilterMap(Rc::new(f)))
+    }
+
+    /// Calls a function with a reference to each element when the chunk is evaluated,
+    /// passing the elements on unchanged.
+    ///
+    /// This is a [`transform`](Chunk::transform), so it is fused with the transforms around
+    /// it and keeps the number of elements known.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::{cell::RefCell, rc::Rc};
+    /// use tailcall_chunk::Chunk;
+    ///
+    /// let seen = Rc::new(RefCell::new(Vec::new()));
+    /// let log = seen.clone();
+    /// let chunk = Chunk::default()
+    ///     .append(1)
+    ///     .append(2)
+    ///     .inspect(move |x| log.borrow_mut().push(*x));
+    ///
+    /// assert!(seen.borrow().is_empty());
+    /// assert_eq!(chunk.as_vec(), vec![1, 2]);
+    /// assert_eq!(*seen.borrow(), vec![1, 2]);
+    /// ```
+    pub fn inspect(self, f: impl Fn(&A) + 'static) -> Self {
+        self.transform(move |a| {
+            f(&a);
+            a
+        })
+    }
+
+    /// Keeps the elements of the chunk up to the first one that doesn't match the predicate.
+    ///
+    /// This is lazy and evaluated inline, like [`filter`](Chunk::filter). Once an element
+    /// doesn't match, the rest of the chunk is skipped without being evaluated whenever
+    /// possible.
+    ///
+    /// # Examples
+    /// ```
+    /// use tailcall_chunk::Chunk;
+    ///
+    /// let chunk: Chunk<_> = vec![1, 2, 5, 1].into();
+    /// assert_eq!(chunk.take_while(|x| *x < 3).as_vec(), vec![1, 2]);
+    /// ```
+    pub fn take_while(self, p: impl Fn(&A) -> bool + 'static) -> Self {
+        self.apply(Op::TakeWhile(Rc::new(p)))
+    }
+
+    /// Drops the elements of the chunk up to the first one that doesn't match the predicate.
+    ///
+    /// This is lazy and evaluated inline, like [`filter`](Chunk::filter).
+    ///
+    /// # Examples
+    /// ```
+    /// use tailcall_chunk::Chunk;
+    ///
+    /// let chunk: Chunk<_> = vec![1, 2, 5, 1].into();
+    /// assert_eq!(chunk.skip_while(|x| *x < 3).as_vec(), vec![5, 1]);
+    /// ```
+    pub fn skip_while(self, p: impl Fn(&A) -> bool + 'static) -> Self {
+        self.apply(Op::SkipWhile(Rc::new(p)))
+    }
+
+    /// Caches the elements of the chunk the first time they are evaluated.
+    ///
+    /// Every read of a chunk normally runs its pending transformations again. A memoized
+    /// chunk evaluates them only once, on the first read through [`as_vec`](Chunk::as_vec),
+    /// [`iter`](Chunk::iter), [`get`](Chunk::get) and the like, and stores the result in the
+    /// node. Later reads, including reads of clones and of chunks built on top of this one,
+    /// reuse the stored elements. Unlike [`materialize`](Chunk::materialize), nothing is
+    /// evaluated until the elements are actually needed.
+    ///
+    /// The first read also fills the caches of the memoized chunks nested in this one,
+    /// innermost first, so that long chains of memoized transformations are evaluated
+    /// without recursion.
+    ///
+    /// Chunks without pending transformations are returned as they are.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::cell::Cell;
+    /// use std::rc::Rc;
+    /// use tailcall_chunk::Chunk;
+    ///
+    /// let calls = Rc::new(Cell::new(0));
+    /// let counter = calls.clone();
+    /// let chunk = Chunk::default()
+    ///     .append(1)
+    ///     .append(2)
+    ///     .transform(move |x| {
+    ///         counter.set(counter.get() + 1);
+    ///         x * 2
+    ///     })
+    ///     .memoize();
+    ///
+    /// assert_eq!(chunk.as_vec(), vec![2, 4]);
+    /// assert_eq!(chunk.clone().append(6).as_vec(), vec![2, 4, 6]);
+    /// assert_eq!(calls.get(), 2);
+    /// ```
+    pub fn memoize(self) -> Self
     where
-        A: Clone,
+        A: 'static,
     {
-        let mut vec = Vec::new();
-        self.as_vec_mut(&mut vec);
-        vec
+        match self.node {
+            Node::Transform(_, _) | Node::Map(_) | Node::Concat(_, _) | Node::Reverse(_) => {
+                let cache: Rc<OnceCell<_>> = Rc::default();
+                Node::Memo(Link::new(self), cache).into()
+            }
+            _ => self,
+        }
+    }
+
+    /// Transforms each element in the chunk like [`transform`](Chunk::transform), caching
+    /// the result the first time it is evaluated.
+    ///
+    /// See [`memoize`](Chunk::memoize). Use `transform` instead when `f` should run again on
+    /// every read, e.g. because it depends on external state.
+    ///
+    /// # Examples
+    /// ```
+    /// use tailcall_chunk::Chunk;
+    ///
+    /// let chunk = Chunk::default().append(1).append(2).transform_memo(|x| x * 2);
+    /// assert_eq!(chunk.as_vec(), vec![2, 4]);
+    /// ```
+    pub fn transform_memo(self, f: impl Fn(A) -> A + 'static) -> Self
+    where
+        A: 'static,
+    {
+        self.transform(f).memoize()
+    }
+
+    /// Transforms each element in the chunk into a new chunk and flattens the result like
+    /// [`transform_flatten`](Chunk::transform_flatten), caching the result the first time
+    /// it is evaluated.
+    ///
+    /// See [`memoize`](Chunk::memoize).
+    ///
+    /// # Examples
+    /// ```
+    /// use tailcall_chunk::Chunk;
+    ///
+    /// let chunk = Chunk::default().append(1).append(2);
+    /// let expanded = chunk.transform_flatten_memo(|x| Chunk::default().append(x).append(x + 1));
+    /// assert_eq!(expanded.as_vec(), vec![1, 2, 2, 3]);
+    /// ```
+    pub fn transform_flatten_memo(self, f: impl Fn(A) -> Chunk<A> + 'static) -> Self
+    where
+        A: 'static,
+    {
+        self.transform_flatten(f).memoize()
+    }
+
+    /// Transforms each element in the chunk into an element of another type.
+    ///
+    /// Like [`transform`](Chunk::transform), this is lazy: `f` only runs when the elements
+    /// are read, e.g. through [`as_vec`](Chunk::as_vec) or [`iter`](Chunk::iter). The source
+    /// chunk is kept behind a type-erased node, so the number of elements stays known.
+    ///
+    /// A map that keeps the element type is a `transform`, and is fused with the transforms
+    /// around it, so chains of them of any length are evaluated in a single pass.
+    ///
+    /// # Performance
+    /// - Creating the transformation: O(1)
+    /// - Executing the transformation: O(n), and every change of element type along a chain
+    ///   of maps adds a level of nested iterators
+    ///
+    /// # Examples
+    /// ```
+    /// use tailcall_chunk::Chunk;
+    ///
+    /// let chunk = Chunk::default().append(1).append(2);
+    /// let strings = chunk.map(|x| x.to_string());
+    /// assert_eq!(strings.len(), Some(2));
+    /// assert_eq!(strings.as_vec(), vec!["1", "2"]);
+    /// ```
+    pub fn map<B>(self, f: impl Fn(A) -> B + 'static) -> Chunk<B>
+    where
+        A: Clone + 'static,
+        B: 'static,
+    {
+        let f: Rc<dyn Fn(A) -> B> = Rc::new(f);
+        match cast::<_, (Chunk<B>, Rc<dyn Fn(B) -> B>)>((self, f)) {
+            Ok((chunk, f)) => chunk.push_map(f),
+            Err((chunk, f)) => Node::Map(Mapped(Rc::new(MapFn { chunk, f }))).into(),
+        }
+    }
+
+    /// Transforms each element in the chunk into a chunk of another type and flattens the
+    /// result.
+    ///
+    /// This is the type-changing counterpart of [`transform_flatten`](Chunk::transform_flatten),
+    /// and is just as lazy. Like [`map`](Chunk::map), a flat map that keeps the element type
+    /// is a `transform_flatten`.
+    ///
+    /// # Examples
+    /// ```
+    /// use tailcall_chunk::Chunk;
+    ///
+    /// let words = Chunk::default().append("ab").append("c");
+    /// let chars = words.flat_map(|w| w.chars().collect());
+    /// assert_eq!(chars.as_vec(), vec!['a', 'b', 'c']);
+    /// ```
+    pub fn flat_map<B>(self, f: impl Fn(A) -> Chunk<B> + 'static) -> Chunk<B>
+    where
+        A: Clone + 'static,
+        B: Clone + 'static,
+    {
+        let f: Rc<dyn Fn(A) -> Chunk<B>> = Rc::new(f);
+        match cast::<_, (Chunk<B>, Rc<dyn Fn(B) -> Chunk<B>>)>((self, f)) {
+            Ok((chunk, f)) => chunk.apply(Op::Flatten(f)),
+            Err((chunk, f)) => Node::Map(Mapped(Rc::new(FlatMapFn { chunk, f }))).into(),
+        }
+    }
+
+    /// Transforms each element in the chunk with a function that can fail.
+    ///
+    /// The result holds the outcome of `f` for every element, and is just as lazy as
+    /// [`map`](Chunk::map). Use [`try_as_vec`](Chunk::try_as_vec) to evaluate it up to the
+    /// first error, or [`try_as_vec_all`](Chunk::try_as_vec_all) to collect every error.
+    ///
+    /// # Examples
+    /// ```
+    /// use tailcall_chunk::Chunk;
+    ///
+    /// let chunk = Chunk::default().append(1).append(2);
+    /// let scaled = chunk.try_transform(|x: i32| x.checked_mul(10).ok_or("overflow"));
+    /// assert_eq!(scaled.try_as_vec(), Ok(vec![10, 20]));
+    ///
+    /// let invalid = Chunk::new(i32::MAX).try_transform(|x| x.checked_mul(10).ok_or("overflow"));
+    /// assert_eq!(invalid.try_as_vec(), Err("overflow"));
+    /// ```
+    pub fn try_transform<E>(self, f: impl Fn(A) -> Result<A, E> + 'static) -> Chunk<Result<A, E>>
+    where
+        A: Clone + 'static,
+        E: 'static,
+    {
+        self.map(f)
+    }
+
+    /// Transforms each element in the chunk into a chunk with a function that can fail, and
+    /// flattens the result.
+    ///
+    /// Every element of a successfully produced chunk becomes an `Ok`, while a failure
+    /// becomes a single `Err`. Like [`try_transform`](Chunk::try_transform), nothing is
+    /// evaluated until the result is read.
+    ///
+    /// # Examples
+    /// ```
+    /// use tailcall_chunk::Chunk;
+    ///
+    /// let chunk = Chunk::default().append(1).append(2);
+    /// let expanded = chunk.try_transform_flatten(|x| match x {
+    ///     1 => Ok(Chunk::default().append(1).append(1)),
+    ///     x => Err(format!("unexpected {x}")),
+    /// });
+    /// assert_eq!(expanded.try_as_vec(), Err("unexpected 2".to_string()));
+    /// ```
+    pub fn try_transform_flatten<E>(
+        self,
+        f: impl Fn(A) -> Result<Chunk<A>, E> + 'static,
+    ) -> Chunk<Result<A, E>>
+    where
+        A: Clone + 'static,
+        E: Clone + 'static,
+    {
+        self.flat_map(move |a| match f(a) {
+            Ok(chunk) => chunk.map(Ok),
+            Err(e) => Chunk::new(Err(e)),
+        })
+    }
+
+    /// Returns a lazy iterator over the elements of the chunk.
+    ///
+    /// Unlike [`as_vec`](Chunk::as_vec), nothing is cloned or evaluated up front:
+    /// elements stored in the chunk are yielded by reference, and pending transformations
+    /// are applied only to the elements that are actually consumed. This makes it cheap to
+    /// stream over a chunk, `take` a few elements or stop early.
+    ///
+    /// # Examples
+    /// ```
+    /// use tailcall_chunk::Chunk;
+    ///
+    /// let chunk = Chunk::default().append(1).append(2).append(3);
+    /// assert_eq!(chunk.iter().map(|a| *a).sum::<i32>(), 6);
+    ///
+    /// let doubled = chunk.transform(|x| x * 2);
+    /// assert_eq!(doubled.iter().nth(1).unwrap().into_owned(), 4);
+    /// ```
+    pub fn iter(&self) -> Iter<'_, A>
+    where
+        A: Clone,
+    {
+        Iter::new(self)
+    }
+
+    /// Returns the element at `index`, or `None` if the index is out of bounds.
+    ///
+    /// The lookup descends through `Concat` nodes using their cached sizes and indexes
+    /// directly into `Collect` nodes, so it takes time proportional to the depth of the
+    /// chunk rather than its length. Pending [`transform`](Chunk::transform)s and
+    /// [`map`](Chunk::map)s keep the number of elements, so the lookup descends through them
+    /// as well and only transforms the requested element. Subtrees whose number of elements
+    /// is unknown, e.g. with a pending [`transform_flatten`](Chunk::transform_flatten), are
+    /// only evaluated when they come before the requested element, and only as far as
+    /// needed to count their elements or reach the element itself.
+    ///
+    /// # Examples
+    /// ```
+    /// use tailcall_chunk::Chunk;
+    ///
+    /// let chunk = Chunk::default().append(1).append(2).prepend(0);
+    /// assert_eq!(chunk.get(1).as_deref(), Some(&1));
+    /// assert_eq!(chunk.get(3), None);
+    ///
+    /// let doubled = chunk.transform(|x| x * 2);
+    /// assert_eq!(doubled.get(2).as_deref(), Some(&4));
+    /// ```
+    pub fn get(&self, index: usize) -> Option<Cow<'_, A>>
+    where
+        A: Clone,
+    {
+        let mut index = index;
+        // The functions of the `Map` operations on the path to the element, outermost first
+        let mut maps = Vec::new();
+        // The subtrees that still have to be searched, leftmost on top
+        let mut stack = vec![self];
+        let elem = 'search: loop {
+            let node = stack.pop()?;
+            if let Some(len) = node.len() {
+                if index >= len {
+                    index -= len;
+                    continue;
+                }
+            }
+            match &node.node {
+                Node::Empty => {}
+                Node::Single(a) => break Cow::Borrowed(a),
+                Node::Collect(vec) => break Cow::Borrowed(vec.get(index)?),
+                Node::Slice(vec, range) => break Cow::Borrowed(vec[range.clone()].get(index)?),
+                Node::Concat(a, b) => {
+                    stack.push(b);
+                    stack.push(a);
+                }
+                Node::Memo(a, cell) => {
+                    let vec = Self::memoized(a, &**cell);
+                    match vec.get(index) {
+                        Some(a) => break Cow::Borrowed(a),
+                        None => index -= vec.len(),
+                    }
+                }
+                Node::Reverse(a) if node.len().is_some() => {
+                    // The element is within `a`, at the same distance from its end
+                    index = a.len().unwrap_or_default() - 1 - index;
+                    stack.push(a);
+                }
+                Node::Transform(a, Op::Map(fs)) if node.len().is_some() => {
+                    // The element is the one at the same index in `a`, transformed
+                    maps.push(fs);
+                    stack.push(a);
+                }
+                Node::Map(mapped) if node.len().is_some() => break Cow::Owned(mapped.get(index)?),
+                Node::Transform(_, _) | Node::Map(_) | Node::Reverse(_) => {
+                    // The elements have to be evaluated, so count them up to `index`
+                    let mut count = 0;
+                    for elem in node.iter() {
+                        if count == index {
+                            break 'search elem;
+                        }
+                        count += 1;
+                    }
+                    index -= count;
+                }
+            }
+        };
+        if maps.is_empty() {
+            return Some(elem);
+        }
+        let a = maps
+            .iter()
+            .rev()
+            .fold(elem.into_owned(), |a, fs| Op::map_all(fs, a));
+        Some(Cow::Owned(a))
+    }
+
+    /// Returns the first element of the chunk, or `None` if it is empty.
+    ///
+    /// See [`get`](Chunk::get) for the cost of the lookup.
+    ///
+    /// # Examples
+    /// ```
+    /// use tailcall_chunk::Chunk;
+    ///
+    /// let chunk = Chunk::default().append(1).append(2);
+    /// assert_eq!(chunk.first().as_deref(), Some(&1));
+    /// ```
+    pub fn first(&self) -> Option<Cow<'_, A>>
+    where
+        A: Clone,
+    {
+        self.get(0)
+    }
+
+    /// Returns the last element of the chunk, or `None` if it is empty.
+    ///
+    /// The chunk is walked backwards from its end, so pending transformations only run on
+    /// the elements from the end up to the last one that makes it through them. Subtrees
+    /// with a pending [`take_while`](Chunk::take_while) or [`skip_while`](Chunk::skip_while)
+    /// on the way are evaluated forwards first, since the elements they keep depend on the
+    /// ones before.
+    ///
+    /// # Examples
+    /// ```
+    /// use tailcall_chunk::Chunk;
+    ///
+    /// let chunk = Chunk::default().append(1).append(2);
+    /// assert_eq!(chunk.last().as_deref(), Some(&2));
+    ///
+    /// let empty: Chunk<i32> = Chunk::default();
+    /// assert_eq!(empty.last(), None);
+    /// ```
+    pub fn last(&self) -> Option<Cow<'_, A>>
+    where
+        A: Clone,
+    {
+        Iter::back(self).next()
+    }
+
+    /// Returns the first element of the chunk along with a chunk of the remaining ones, or
+    /// `None` if it is empty. The chunk itself is left untouched.
+    ///
+    /// # Performance
+    /// Proportional to the depth of the first element, as the nodes on the path to it are
+    /// rebuilt around the remaining elements, which are never copied. Along the way, a
+    /// `Concat` node whose left child is itself a `Concat` is rotated to the right, like the
+    /// digits of a finger tree, so the next element ends up close to the root: taking all
+    /// the elements off the front one at a time costs amortized O(1) each.
+    ///
+    /// Subtrees on the path with a pending transformation other than
+    /// [`transform`](Chunk::transform) are evaluated into a vector first, which the remaining
+    /// chunk then shares.
+    ///
+    /// # Examples
+    /// ```
+    /// use tailcall_chunk::Chunk;
+    ///
+    /// let chunk = Chunk::default().append(1).append(2).append(3);
+    /// let (first, rest) = chunk.uncons().unwrap();
+    /// assert_eq!(first, 1);
+    /// assert_eq!(rest.as_vec(), vec![2, 3]);
+    /// assert_eq!(chunk.as_vec(), vec![1, 2, 3]);
+    /// ```
+    pub fn uncons(&self) -> Option<(A, Chunk<A>)>
+    where
+        A: Clone,
+    {
+        self.clone().split_end(false)
+    }
+
+    /// Returns a chunk of all the elements but the last, along with the last element, or
+    /// `None` if the chunk is empty. The chunk itself is left untouched.
+    ///
+    /// # Performance
+    /// The mirror image of [`uncons`](Chunk::uncons): nested `Concat` nodes on the right
+    /// edge are rotated to the left, so taking all the elements off the back one at a time
+    /// costs amortized O(1) each.
+    ///
+    /// # Examples
+    /// ```
+    /// use tailcall_chunk::Chunk;
+    ///
+    /// let chunk = Chunk::default().append(1).append(2).append(3);
+    /// let (rest, last) = chunk.unsnoc().unwrap();
+    /// assert_eq!(rest.as_vec(), vec![1, 2]);
+    /// assert_eq!(last, 3);
+    /// ```
+    pub fn unsnoc(&self) -> Option<(Chunk<A>, A)>
+    where
+        A: Clone,
+    {
+        let (a, rest) = self.clone().split_end(true)?;
+        Some((rest, a))
+    }
+
+    /// Removes the first element of the chunk and returns it, or `None` if it is empty.
+    ///
+    /// See [`uncons`](Chunk::uncons) for the cost. Other versions that share nodes with this
+    /// chunk are not affected.
+    ///
+    /// # Examples
+    /// ```
+    /// use tailcall_chunk::Chunk;
+    ///
+    /// let mut queue = Chunk::default().append("a").append("b");
+    /// let snapshot = queue.clone();
+    /// assert_eq!(queue.pop_front(), Some("a"));
+    /// queue = queue.append("c");
+    /// assert_eq!(queue.pop_front(), Some("b"));
+    /// assert_eq!(queue.as_vec(), vec!["c"]);
+    /// assert_eq!(snapshot.as_vec(), vec!["a", "b"]);
+    /// ```
+    pub fn pop_front(&mut self) -> Option<A>
+    where
+        A: Clone,
+    {
+        let (a, rest) = core::mem::take(self).split_end(false)?;
+        *self = rest;
+        Some(a)
+    }
+
+    /// Removes the last element of the chunk and returns it, or `None` if it is empty.
+    ///
+    /// See [`unsnoc`](Chunk::unsnoc) for the cost. Other versions that share nodes with this
+    /// chunk are not affected.
+    ///
+    /// # Examples
+    /// ```
+    /// use tailcall_chunk::Chunk;
+    ///
+    /// let mut stack = Chunk::default().append(1).append(2);
+    /// assert_eq!(stack.pop_back(), Some(2));
+    /// assert_eq!(stack.pop_back(), Some(1));
+    /// assert_eq!(stack.pop_back(), None);
+    /// ```
+    pub fn pop_back(&mut self) -> Option<A>
+    where
+        A: Clone,
+    {
+        let (a, rest) = core::mem::take(self).split_end(true)?;
+        *self = rest;
+        Some(a)
+    }
+
+    /// Converts the chunk into a vector of references to its elements.
+    ///
+    /// This operation has O(n) complexity where n is the number of elements
+    /// in the chunk.
+    ///
+    /// # Examples
+    /// ```
+    /// use tailcall_chunk::Chunk;
+    ///
+    /// let chunk = Chunk::default().append(1).append(2).append(3);
+    /// assert_eq!(chunk.as_vec(), vec![1, 2, 3]);
+    /// ```
+    pub fn as_vec(&self) -> Vec<A>
+    where
+        A: Clone,
+    {
+        let mut vec = Vec::with_capacity(self.size_hint().0);
+        self.as_vec_mut(&mut vec);
+        vec
+    }
+
+    /// Converts the chunk into a vector of its elements, consuming the chunk.
+    ///
+    /// Elements are moved out of the nodes that are uniquely owned by this chunk, so
+    /// unlike [`as_vec`](Chunk::as_vec) they are not cloned. Only the elements of nodes
+    /// that are still shared with another chunk are cloned.
+    ///
+    /// # Examples
+    /// ```
+    /// use tailcall_chunk::Chunk;
+    ///
+    /// let chunk = Chunk::default().append(String::from("a")).append(String::from("b"));
+    /// assert_eq!(chunk.into_vec(), vec!["a", "b"]);
+    /// ```
+    pub fn into_vec(self) -> Vec<A>
+    where
+        A: Clone,
+    {
+        self.into_iter().collect()
+    }
+
+    /// Converts the chunk into a vector of its elements without requiring `A: Clone`.
+    ///
+    /// Elements are moved out of the nodes that are uniquely owned by this chunk.
+    ///
+    /// # Errors
+    /// Returns [`SharedElementError`] if an element lives in a node that is still shared
+    /// with another chunk (e.g. a clone of this one), since it can neither be moved nor
+    /// cloned. The remaining elements are dropped in that case.
+    ///
+    /// # Examples
+    /// ```
+    /// use tailcall_chunk::{Chunk, SharedElementError};
+    ///
+    /// // `Box<dyn Error>` can't be cloned
+    /// let errors: Chunk<Box<dyn std::error::Error>> = Chunk::default()
+    ///     .append("first".into())
+    ///     .append("second".into());
+    /// let errors = errors.try_into_vec().unwrap();
+    /// assert_eq!(errors[1].to_string(), "second");
+    ///
+    /// let shared: Chunk<_> = vec![std::cell::Cell::new(1)].into_iter().collect();
+    /// let version = shared.clone();
+    /// assert_eq!(shared.try_into_vec(), Err(SharedElementError));
+    /// ```
+    pub fn try_into_vec(self) -> Result<Vec<A>, SharedElementError> {
+        IntoIter::new(self).try_collect()
+    }
+
+    /// Returns a consuming iterator over the elements of the chunk without requiring
+    /// `A: Clone`.
+    ///
+    /// This is the lazy counterpart of [`try_into_vec`](Chunk::try_into_vec): elements are
+    /// moved out one at a time, and pending transformations are only applied to the elements
+    /// that are actually consumed. An element of a node that is still shared with another
+    /// chunk is yielded as `Err(SharedElementError)`, which ends the iteration.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::fs::File;
+    /// use tailcall_chunk::Chunk;
+    ///
+    /// // `File` can't be cloned
+    /// let files: Chunk<File> = Chunk::default();
+    /// for file in files.try_into_iter() {
+    ///     let _file: File = file.unwrap();
+    /// }
+    /// ```
+    pub fn try_into_iter(self) -> TryIntoIter<A> {
+        TryIntoIter::new(self)
+    }
+
+    /// Helper method that populates a vector with references to the chunk's elements.
+    ///
+    /// This method is used internally by [`as_vec`](Chunk::as_vec) to avoid
+    /// allocating multiple vectors during the traversal.
+    ///
+    /// Elements stream from the nodes of the chunk through its pending transformations
+    /// straight into `buf`, so the source of a transformation is never collected first.
+    /// Besides `buf`, the traversal only keeps an explicit stack proportional to the depth
+    /// of the chunk, so chunks of any depth (e.g. built from millions of
+    /// [`prepend`](Chunk::prepend) calls) can be materialized without overflowing the call
+    /// stack.
+    ///
+    /// # Arguments
+    /// * `buf` - A mutable reference to a vector that will be populated with
+    ///   references to the chunk's elements
+    pub fn as_vec_mut(&self, buf: &mut Vec<A>)
+    where
+        A: Clone,
+    {
+        buf.reserve(self.size_hint().0);
+        let _ = self.send_to(buf);
+    }
+
+    /// Streams the elements of the chunk into `sink`, in order, until it stops accepting
+    /// them.
+    ///
+    /// Like [`as_vec_mut`](Chunk::as_vec_mut), pending transformations are evaluated on the
+    /// fly and only an explicit stack proportional to the depth of the chunk is kept, so no
+    /// staging `Vec` is built. Elements after the one the sink refused are never evaluated.
+    ///
+    /// Returns `Break` if the sink stopped accepting elements.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::{collections::VecDeque, ops::ControlFlow};
+    /// use tailcall_chunk::Chunk;
+    ///
+    /// let mut queue = VecDeque::from([0]);
+    /// let chunk = Chunk::default().append(1).append(2).transform(|x| x * 10);
+    /// assert_eq!(chunk.send_to(&mut queue), ControlFlow::Continue(()));
+    /// assert_eq!(queue, [0, 10, 20]);
+    /// ```
+    pub fn send_to(&self, sink: &mut impl Sink<A>) -> ControlFlow<()>
+    where
+        A: Clone,
+    {
+        // The subtrees that still have to be sent, leftmost on top
+        let mut stack = vec![self];
+        while let Some(chunk) = stack.pop() {
+            match &chunk.node {
+                Node::Empty => {}
+                Node::Single(a) => sink.accept(a.clone())?,
+                Node::Collect(vec) => sink.accept_all(vec.iter().cloned())?,
+                Node::Slice(vec, range) => sink.accept_all(vec[range.clone()].iter().cloned())?,
+                Node::Concat(a, b) => {
+                    stack.push(b);
+                    stack.push(a);
+                }
+                Node::Transform(_, _) | Node::Reverse(_) => chunk.iter().send_to(sink)?,
+                Node::Map(mapped) => sink.accept_all(mapped.iter(false))?,
+                Node::Memo(a, cell) => {
+                    sink.accept_all(Self::memoized(a, &**cell).iter().cloned())?
+                }
+            }
+        }
+        ControlFlow::Continue(())
+    }
+
+    /// Adds the elements of the chunk to any collection that implements [`Extend`].
+    ///
+    /// # Examples
+    /// ```
+    /// use std::collections::HashSet;
+    /// use tailcall_chunk::Chunk;
+    ///
+    /// let mut seen = HashSet::from([1]);
+    /// Chunk::default().append(1).append(2).extend_into(&mut seen);
+    /// assert_eq!(seen, HashSet::from([1, 2]));
+    /// ```
+    pub fn extend_into<E: Extend<A>>(&self, collection: &mut E)
+    where
+        A: Clone,
+    {
+        let _ = self.send_to(&mut Extender(collection));
+    }
+
+    /// Calls `f` on each element of the chunk, in order.
+    ///
+    /// # Examples
+    /// ```
+    /// use tailcall_chunk::Chunk;
+    ///
+    /// let mut sum = 0;
+    /// Chunk::default().append(1).append(2).for_each(|x| sum += x);
+    /// assert_eq!(sum, 3);
+    /// ```
+    pub fn for_each(&self, mut f: impl FnMut(A))
+    where
+        A: Clone,
+    {
+        let _ = self.try_for_each(|a| {
+            f(a);
+            ControlFlow::<()>::Continue(())
+        });
+    }
+
+    /// Calls `f` on each element of the chunk, in order, stopping as soon as it returns
+    /// `Break`.
+    ///
+    /// Elements after the one `f` broke on are never evaluated. Returns the value `f` broke
+    /// with, if any.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::ops::ControlFlow;
+    /// use tailcall_chunk::Chunk;
+    ///
+    /// let chunk: Chunk<_> = (1..=5).collect();
+    /// let mut sum = 0;
+    /// let found = chunk.try_for_each(|x| {
+    ///     sum += x;
+    ///     if sum > 5 {
+    ///         ControlFlow::Break(x)
+    ///     } else {
+    ///         ControlFlow::Continue(())
+    ///     }
+    /// });
+    /// assert_eq!(found, ControlFlow::Break(3));
+    /// ```
+    pub fn try_for_each<B>(&self, f: impl FnMut(A) -> ControlFlow<B>) -> ControlFlow<B>
+    where
+        A: Clone,
+    {
+        let mut sink = TryForEach { f, broken: None };
+        let _ = self.send_to(&mut sink);
+        match sink.broken {
+            Some(b) => ControlFlow::Break(b),
+            None => ControlFlow::Continue(()),
+        }
+    }
+}
+
+impl<A: Clone, E: Clone> Chunk<Result<A, E>> {
+    /// Converts a chunk of results into a vector of its `Ok` values, stopping at the first
+    /// error.
+    ///
+    /// Elements after the first error are never evaluated, so the remaining work of a
+    /// [`try_transform`](Chunk::try_transform) is skipped.
+    ///
+    /// # Examples
+    /// ```
+    /// use tailcall_chunk::Chunk;
+    ///
+    /// let chunk = Chunk::default().append(Ok(1)).append(Err("a")).append(Err("b"));
+    /// assert_eq!(chunk.try_as_vec(), Err("a"));
+    /// ```
+    pub fn try_as_vec(&self) -> Result<Vec<A>, E> {
+        self.iter().map(Cow::into_owned).collect()
+    }
+
+    /// Converts a chunk of results into a vector of its `Ok` values, or into every error it
+    /// contains if there is at least one.
+    ///
+    /// Unlike [`try_as_vec`](Chunk::try_as_vec), all elements are evaluated, and the errors
+    /// are returned in order.
+    ///
+    /// # Examples
+    /// ```
+    /// use tailcall_chunk::Chunk;
+    ///
+    /// let chunk = Chunk::default().append(Ok(1)).append(Err("a")).append(Err("b"));
+    /// let errors = chunk.try_as_vec_all().unwrap_err();
+    /// assert_eq!(errors.as_vec(), vec!["a", "b"]);
+    /// ```
+    pub fn try_as_vec_all(&self) -> Result<Vec<A>, Chunk<E>> {
+        let mut values = Vec::with_capacity(self.size_hint().0);
+        let mut errors = Vec::new();
+        for result in self.iter() {
+            match result.into_owned() {
+                Ok(a) => values.push(a),
+                Err(e) => errors.push(e),
+            }
+        }
+        if errors.is_empty() {
+            Ok(values)
+        } else {
+            Err(errors.into_iter().collect())
+        }
+    }
+}
+
+impl<A: Clone> Chunk<A> {
+    /// Returns the cached elements of a `Memo` node, evaluating `source` if needed.
+    ///
+    /// The memoized chunks nested in `source` are evaluated first, innermost first, so that
+    /// evaluating one never has to evaluate another recursively.
+    pub(crate) fn memoized<'a>(source: &Link<A>, cell: &'a dyn MemoCache<A>) -> &'a Rc<Vec<A>> {
+        if let Some(vec) = cell.get() {
+            return vec;
+        }
+        for (source, cell) in source.pending_memos().into_iter().rev() {
+            if cell.get().is_none() {
+                cell.set(Rc::new(source.as_vec()));
+            }
+        }
+        cell.set(Rc::new(source.as_vec()))
+    }
+
+    /// Takes the chunk out of a link, copying its root node if it is shared.
+    fn from_link(mut link: Link<A>) -> Self {
+        link.take_unique().unwrap_or_else(|| (*link).clone())
+    }
+
+    /// Removes the element at the front of the chunk, or at its back if `back` is set, and
+    /// returns it along with the remaining elements.
+    ///
+    /// The descent towards the element records how to rebuild each node around the
+    /// remaining elements of its subtree, and rotates nested `Concat` nodes on the near edge
+    /// towards the far side. It backtracks past subtrees that turn out to be empty.
+    fn split_end(self, mut back: bool) -> Option<(A, Chunk<A>)> {
+        // The nodes above the current subtree, innermost on top
+        let mut holes = Vec::new();
+        let mut chunk = self;
+        let (mut a, mut rest) = loop {
+            match chunk.node {
+                Node::Single(a) => break (a, Chunk::default()),
+                Node::Collect(vec) => {
+                    let range = 0..vec.len();
+                    chunk = Node::Slice(vec, range).into();
+                }
+                Node::Slice(vec, mut range) if !range.is_empty() => {
+                    let index = if back { range.end - 1 } else { range.start };
+                    let a = vec[index].clone();
+                    if back {
+                        range.end -= 1;
+                    } else {
+                        range.start += 1;
+                    }
+                    let rest = if range.is_empty() {
+                        Chunk::default()
+                    } else {
+                        Node::Slice(vec, range).into()
+                    };
+                    break (a, rest);
+                }
+                Node::Concat(a, b) => {
+                    let (near, far) = if back { (b, a) } else { (a, b) };
+                    let far = Self::from_link(far);
+                    match (Self::from_link(near).node, back) {
+                        (Node::Concat(x, y), false) => {
+                            holes.push(Hole::Before(Self::from_link(y).concat(far)));
+                            chunk = Self::from_link(x);
+                        }
+                        (Node::Concat(x, y), true) => {
+                            holes.push(Hole::After(far.concat(Self::from_link(x))));
+                            chunk = Self::from_link(y);
+                        }
+                        (near, false) => {
+                            holes.push(Hole::Before(far));
+                            chunk = near.into();
+                        }
+                        (near, true) => {
+                            holes.push(Hole::After(far));
+                            chunk = near.into();
+                        }
+                    }
+                }
+                Node::Transform(source, Op::Map(fs)) => {
+                    holes.push(Hole::Map(fs));
+                    chunk = Self::from_link(source);
+                }
+                Node::Reverse(source) => {
+                    holes.push(Hole::Reverse);
+                    back = !back;
+                    chunk = Self::from_link(source);
+                }
+                Node::Memo(source, cell) => {
+                    chunk = Node::Collect(Self::memoized(&source, &*cell).clone()).into();
+                }
+                node @ (Node::Transform(_, _) | Node::Map(_)) => {
+                    chunk = Node::Collect(Rc::new(Chunk::from(node).as_vec())).into();
+                }
+                Node::Empty | Node::Slice(_, _) => loop {
+                    // Carry on with the closest subtree on the far side
+                    match holes.pop()? {
+                        Hole::Before(far) | Hole::After(far) => {
+                            chunk = far;
+                            break;
+                        }
+                        Hole::Map(_) => {}
+                        Hole::Reverse => back = !back,
+                    }
+                },
+            }
+        };
+
+        while let Some(hole) = holes.pop() {
+            match hole {
+                Hole::Before(far) => rest = rest.concat(far),
+                Hole::After(far) => rest = far.concat(rest),
+                Hole::Map(fs) => {
+                    a = Op::map_all(&fs, a);
+                    if !rest.is_null() {
+                        rest = rest.apply(Op::Map(fs));
+                    }
+                }
+                Hole::Reverse => rest = rest.reverse(),
+            }
+        }
+        Some((a, rest))
+    }
+}
+
+/// How to rebuild a node around the remaining elements of one of its subtrees, after an
+/// element was split off the subtree by [`Chunk::split_end`].
+enum Hole<A> {
+    /// The subtree is followed by the given chunk
+    Before(Chunk<A>),
+    /// The subtree is preceded by the given chunk
+    After(Chunk<A>),
+    /// The subtree is the source of a `Map` operation
+    Map(Functions<A>),
+    /// The subtree is reversed
+    Reverse,
+}
+
+/// A shared reference to a child chunk.
+///
+/// `Link` behaves like an [`Rc<Chunk<A>>`](Rc): cloning it is O(1) and the child is shared
+/// between all versions that point to it. In addition, dropping the last reference to a deep
+/// subtree releases its nodes iteratively rather than through recursive drop glue, so freeing
+/// a chunk is stack-safe no matter how many `Concat` or `Transform` levels it has.
+pub(crate) struct Link<A>(Rc<Linked<A>>);
+
+/// The shared allocation behind a [`Link`]: the child chunk along with its cached size hint.
+struct Linked<A> {
+    chunk: Chunk<A>,
+    size_hint: (usize, Option<usize>),
+    depth: usize,
+    leaves: usize,
+}
+
+impl<A> Link<A> {
+    /// Creates a new link owning the given chunk.
+    pub fn new(chunk: Chunk<A>) -> Self {
+        let size_hint = chunk.size_hint();
+        let depth = chunk.depth();
+        let leaves = chunk.leaves();
+        Link(Rc::new(Linked {
+            chunk,
+            size_hint,
+            depth,
+            leaves,
+        }))
+    }
+
+    /// Returns the cached bounds on the number of elements of the linked chunk.
+    ///
+    /// See [`Chunk::size_hint`].
+    pub fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint
+    }
+
+    /// Takes the chunk out of this link if no other link shares it, leaving an empty chunk
+    /// in its place.
+    pub(crate) fn take_unique(&mut self) -> Option<Chunk<A>> {
+        Rc::get_mut(&mut self.0).map(|linked| core::mem::take(&mut linked.chunk))
+    }
+}
+
+impl<A> Clone for Link<A> {
+    fn clone(&self) -> Self {
+        Link(self.0.clone())
+    }
+}
+
+impl<A> Deref for Link<A> {
+    type Target = Chunk<A>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0.chunk
+    }
+}
+
+impl<A> From<Chunk<A>> for Link<A> {
+    fn from(chunk: Chunk<A>) -> Self {
+        Link::new(chunk)
+    }
+}
+
+impl<A> Drop for Link<A> {
+    /// Releases the linked chunk without recursing into its children.
+    ///
+    /// Every uniquely owned child is unlinked and pushed onto an explicit stack before its
+    /// parent is freed, so each node is dropped only once it no longer has children.
+    /// Children that are still shared with other versions are left untouched.
+    fn drop(&mut self) {
+        let mut sources = Vec::new();
+        if let Some(chunk) = self.take_unique() {
+            chunk.release_into(&mut sources);
+        }
+        release_all(sources);
+    }
+}
+
+/// A lazily transformed chunk of another element type, produced by [`Chunk::map`] and
+/// [`Chunk::flat_map`].
+///
+/// The element type of the source chunk is erased, so that the result can be stored in a
+/// `Chunk<A>` like any other node. Cloning it is O(1).
+pub(crate) struct Mapped<A>(Rc<dyn Source<A>>);
+
+impl<A> Mapped<A> {
+    /// Returns an iterator that evaluates the transformation, from the end if `rev` is set.
+    pub(crate) fn iter(&self, rev: bool) -> Box<dyn Iterator<Item = A>> {
+        self.0.clone().iter(rev)
+    }
+
+    /// Returns the element at `index`, evaluating only what is needed to reach it.
+    pub(crate) fn get(&self, index: usize) -> Option<A> {
+        self.0.get(index)
+    }
+}
+
+impl<A> Clone for Mapped<A> {
+    fn clone(&self) -> Self {
+        Mapped(self.0.clone())
+    }
+}
+
+impl<A> Drop for Mapped<A> {
+    /// Releases the source chunk of the last reference like [`Link`] does, so that chains of
+    /// maps over different element types are released iteratively as well.
+    fn drop(&mut self) {
+        if let Some(source) = Rc::get_mut(&mut self.0).and_then(|source| source.take()) {
+            release_all(vec![source]);
+        }
+    }
+}
+
+/// A source of elements of type `B` behind a [`Mapped`] node.
+trait Source<B> {
+    fn size_hint(&self) -> (usize, Option<usize>);
+
+    fn iter(self: Rc<Self>, rev: bool) -> Box<dyn Iterator<Item = B>>;
+
+    fn get(&self, index: usize) -> Option<B>;
+
+    /// Takes the source chunk out, if it has children that have to be released.
+    fn take(&mut self) -> Option<Box<dyn Release>>;
+}
+
+/// A chunk whose element type is erased, so that the source chunks of `Map` nodes can be
+/// released by the same loop as the chunks that map them.
+trait Release {
+    fn release_into(self: Box<Self>, sources: &mut Vec<Box<dyn Release>>);
+}
+
+impl<A> Release for Chunk<A> {
+    fn release_into(self: Box<Self>, sources: &mut Vec<Box<dyn Release>>) {
+        Chunk::release_into(*self, sources)
+    }
+}
+
+/// Releases `sources`, along with the sources of the `Map` nodes they contain.
+fn release_all(mut sources: Vec<Box<dyn Release>>) {
+    while let Some(source) = sources.pop() {
+        source.release_into(&mut sources);
+    }
+}
+
+/// Returns `a` as a `B` if both are the same type, or gives it back otherwise.
+fn cast<A: 'static, B: 'static>(a: A) -> Result<B, A> {
+    let mut a = Some(a);
+    let b = (&mut a as &mut dyn Any)
+        .downcast_mut::<Option<B>>()
+        .and_then(Option::take);
+    b.ok_or_else(|| a.expect("the value is only taken when it is a `B`"))
+}
+
+/// Returns `chunk` reversed if `rev` is set, so that a [`Source`] can be walked from the end
+/// one element at a time.
+fn reversed_if<A: Clone>(chunk: Chunk<A>, rev: bool) -> Chunk<A> {
+    if rev {
+        chunk.reverse()
+    } else {
+        chunk
+    }
+}
+
+/// Takes `chunk` out of a [`Source`], unless its root node has no children.
+fn take_source<A: 'static>(chunk: &mut Chunk<A>) -> Option<Box<dyn Release>> {
+    match chunk.node {
+        Node::Empty | Node::Single(_) | Node::Collect(_) | Node::Slice(_, _) => None,
+        _ => Some(Box::new(core::mem::take(chunk))),
+    }
+}
+
+struct MapFn<A, B> {
+    chunk: Chunk<A>,
+    f: Rc<dyn Fn(A) -> B>,
+}
+
+impl<A: Clone + 'static, B: 'static> Source<B> for MapFn<A, B> {
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.chunk.size_hint()
+    }
+
+    fn iter(self: Rc<Self>, rev: bool) -> Box<dyn Iterator<Item = B>> {
+        let source = reversed_if(self.chunk.clone(), rev).into_iter();
+        Box::new(source.map(move |a| (self.f)(a)))
+    }
+
+    fn get(&self, index: usize) -> Option<B> {
+        let a = self.chunk.get(index)?.into_owned();
+        Some((self.f)(a))
+    }
+
+    fn take(&mut self) -> Option<Box<dyn Release>> {
+        take_source(&mut self.chunk)
+    }
+}
+
+struct FlatMapFn<A, B> {
+    chunk: Chunk<A>,
+    f: Rc<dyn Fn(A) -> Chunk<B>>,
+}
+
+impl<A: Clone + 'static, B: Clone + 'static> Source<B> for FlatMapFn<A, B> {
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.chunk.size_hint() {
+            (_, Some(0)) => (0, Some(0)),
+            _ => (0, None),
+        }
+    }
+
+    fn iter(self: Rc<Self>, rev: bool) -> Box<dyn Iterator<Item = B>> {
+        let source = reversed_if(self.chunk.clone(), rev).into_iter();
+        Box::new(source.flat_map(move |a| reversed_if((self.f)(a), rev)))
+    }
+
+    fn get(&self, index: usize) -> Option<B> {
+        let mut source = self.chunk.iter().flat_map(|a| (self.f)(a.into_owned()));
+        source.nth(index)
+    }
+
+    fn take(&mut self) -> Option<Box<dyn Release>> {
+        take_source(&mut self.chunk)
+    }
+}
+
+impl<A> Chunk<A> {
+    /// Returns the number of nested `Concat` levels at the top of the chunk.
+    ///
+    /// Nodes of any other kind count as leaves of depth 0, since rebalancing can't look
+    /// through them.
+    fn depth(&self) -> usize {
+        match &self.node {
+            Node::Concat(a, b) => 1 + a.0.depth.max(b.0.depth),
+            _ => 0,
+        }
+    }
+
+    /// Returns the number of leaves below the `Concat` levels at the top of the chunk.
+    fn leaves(&self) -> usize {
+        match &self.node {
+            Node::Concat(a, b) => a.0.leaves.saturating_add(b.0.leaves),
+            _ => 1,
+        }
+    }
+
+    /// Returns the depth of a perfectly balanced tree with `leaves` leaves, rounded up.
+    fn balanced_depth(leaves: usize) -> usize {
+        (usize::BITS - leaves.leading_zeros()) as usize
+    }
+
+    /// Returns `true` if the depth of the chunk is within twice the depth of a perfectly
+    /// balanced tree with the same number of leaves.
+    fn is_balanced(depth: usize, leaves: usize) -> bool {
+        depth <= 2 * Self::balanced_depth(leaves)
+    }
+
+    /// Replaces every piece that doesn't `fit` with its children, recursively, keeping the
+    /// order of the pieces.
+    fn open_pieces(pieces: Vec<Link<A>>, fit: impl Fn(usize, usize) -> bool) -> Vec<Link<A>> {
+        let mut open = Vec::with_capacity(pieces.len());
+        let mut stack = pieces;
+        stack.reverse();
+        while let Some(mut link) = stack.pop() {
+            if fit(link.0.depth, link.0.leaves) || !matches!(link.node, Node::Concat(..)) {
+                open.push(link);
+            } else if let Some(Node::Concat(a, b)) = link.take_unique().map(|chunk| chunk.node) {
+                stack.push(b);
+                stack.push(a);
+            } else if let Node::Concat(a, b) = &link.node {
+                stack.push(b.clone());
+                stack.push(a.clone());
+            }
+        }
+        open
+    }
+
+    /// Builds a tree of depth at most `budget` over `pieces`, keeping their order.
+    fn build_balanced(mut pieces: Vec<Link<A>>, budget: usize) -> Link<A> {
+        if pieces.len() == 1 && pieces[0].0.depth <= budget {
+            pieces.pop().unwrap()
+        } else {
+            Link::new(Self::concat_pieces(pieces, budget))
+        }
+    }
+
+    /// Joins `pieces` under a new `Concat` node of depth at most `budget`.
+    ///
+    /// Pieces too deep to sit below the new node are opened up first. The remaining pieces
+    /// are then split where the number of leaves on both sides is closest to even, so that
+    /// every piece ends up at a depth proportional to the logarithm of the total number of
+    /// leaves divided by its own.
+    fn concat_pieces(pieces: Vec<Link<A>>, budget: usize) -> Chunk<A> {
+        let budget = budget.saturating_sub(1);
+        let mut pieces = Self::open_pieces(pieces, |depth, _| depth <= budget);
+        let total = pieces
+            .iter()
+            .map(|p| p.0.leaves)
+            .fold(0, usize::saturating_add);
+        let (mut split, mut left, mut best) = (1, 0usize, usize::MAX);
+        for (i, piece) in pieces.iter().enumerate().take(pieces.len() - 1) {
+            left = left.saturating_add(piece.0.leaves);
+            let imbalance = left.abs_diff(total - left);
+            if imbalance < best {
+                (split, best) = (i + 1, imbalance);
+            }
+        }
+        let right = pieces.split_off(split);
+        Node::Concat(
+            Self::build_balanced(pieces, budget),
+            Self::build_balanced(right, budget),
+        )
+        .into()
+    }
+
+    /// Returns the source and cache of every `Memo` node in the chunk that hasn't been
+    /// evaluated yet, each one before the nodes nested in its source.
+    pub(crate) fn pending_memos(&self) -> Vec<(&Link<A>, &dyn MemoCache<A>)> {
+        let mut memos = Vec::new();
+        // The subtrees that still have to be searched, leftmost on top
+        let mut stack = vec![self];
+        while let Some(chunk) = stack.pop() {
+            match &chunk.node {
+                Node::Concat(a, b) => {
+                    stack.push(b);
+                    stack.push(a);
+                }
+                Node::Transform(a, _) | Node::Reverse(a) => stack.push(a),
+                Node::Memo(a, cell) if cell.get().is_none() => {
+                    memos.push((a, &**cell));
+                    stack.push(a);
+                }
+                _ => {}
+            }
+        }
+        memos
+    }
+
+    /// Wraps the chunk in a `Transform` node that lazily applies `op` to its elements.
+    fn apply(self, op: Op<A>) -> Self {
+        Node::Transform(Link::new(self), op).into()
+    }
+
+    /// Adds `f` to the `Map` operation at the root of the chunk, or wraps the chunk in a new
+    /// one.
+    fn push_map(self, f: Rc<dyn Fn(A) -> A>) -> Self {
+        match self.node {
+            Node::Transform(a, Op::Map(mut fs)) => {
+                // Pushes in place if no other version shares the functions
+                Rc::make_mut(&mut fs).push(f);
+                Node::Transform(a, Op::Map(fs)).into()
+            }
+            node => Chunk::from(node).apply(Op::Map(Rc::new(vec![f]))),
+        }
     }
 
-    /// Helper method that populates a vector with references to the chunk's elements.
-    ///
-    /// This method is used internally by [`as_vec`](Chunk::as_vec) to avoid
-    /// allocating multiple vectors during the traversal.
+    /// Releases the nodes of the chunk that no other version shares, using an explicit stack.
     ///
-    /// # Arguments
-    /// * `buf` - A mutable reference to a vector that will be populated with
-    ///           references to the chunk's elements
-    pub fn as_vec_mut(&self, buf: &mut Vec<A>)
-    where
-        A: Clone,
-    {
-        match self {
-            Chunk::Empty => {}
-            Chunk::Single(a) => {
-                buf.push(a.clone());
+    /// The sources of the `Map` nodes found along the way have another element type, so they
+    /// are pushed onto `sources` instead.
+    fn release_into(self, sources: &mut Vec<Box<dyn Release>>) {
+        let mut stack = Vec::new();
+        self.unlink_into(&mut stack, sources);
+        while let Some(mut link) = stack.pop() {
+            if let Some(chunk) = link.take_unique() {
+                chunk.unlink_into(&mut stack, sources);
             }
-            Chunk::Concat(a, b) => {
-                a.as_vec_mut(buf);
-                b.as_vec_mut(buf);
-            }
-            Chunk::TransformFlatten(a, f) => {
-                let mut tmp = Vec::new();
-                a.as_vec_mut(&mut tmp);
-                for elem in tmp.into_iter() {
-                    f(elem).as_vec_mut(buf);
-                }
+        }
+    }
+
+    /// Moves the child links of this node onto `stack`, and the source of a `Map` node onto
+    /// `sources`, dropping the node itself.
+    fn unlink_into(self, stack: &mut Vec<Link<A>>, sources: &mut Vec<Box<dyn Release>>) {
+        match self.node {
+            Node::Concat(a, b) => {
+                stack.push(a);
+                stack.push(b);
             }
-            Chunk::Collect(vec) => {
-                buf.extend(vec.borrow().iter().cloned());
+            Node::Transform(a, _) | Node::Memo(a, _) | Node::Reverse(a) => stack.push(a),
+            Node::Map(mut mapped) => {
+                sources.extend(Rc::get_mut(&mut mapped.0).and_then(|source| source.take()));
             }
+            Node::Empty | Node::Single(_) | Node::Collect(_) | Node::Slice(_, _) => {}
         }
     }
 }
@@ -351,7 +2058,22 @@ impl<A> FromIterator<A> for Chunk<A> {
     fn from_iter<T: IntoIterator<Item = A>>(iter: T) -> Self {
         let vec: Vec<_> = iter.into_iter().collect();
 
-        Chunk::Collect(Rc::new(RefCell::new(vec)))
+        Node::Collect(Rc::new(vec)).into()
+    }
+}
+
+impl<A: fmt::Debug + Clone> fmt::Debug for Chunk<A> {
+    /// Formats the chunk as a list of its elements, like a `Vec`.
+    ///
+    /// # Examples
+    /// ```
+    /// use tailcall_chunk::Chunk;
+    ///
+    /// let chunk = Chunk::default().append(1).append(2).transform(|x| x * 10);
+    /// assert_eq!(format!("{:?}", chunk), "[10, 20]");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
     }
 }
 
@@ -501,6 +2223,40 @@ mod tests {
         assert_eq!(result.as_vec(), vec![9, 15, 21]);
     }
 
+    #[test]
+    fn test_transform_fusion() {
+        let chunk: Chunk<_> = (1..=3).collect();
+        let fused = chunk
+            .clone()
+            .transform(|x| x + 1)
+            .transform(|x| x * 10)
+            .transform(|x| x - 1);
+        assert_eq!(fused.len(), Some(3));
+        assert_eq!(fused.as_vec(), vec![19, 29, 39]);
+        assert_eq!(fused.iter().map(|x| *x).collect::<Vec<_>>(), fused.as_vec());
+
+        // The whole chain is a single node over the source
+        let ChunkNode::Transform(source) = fused.view() else {
+            panic!("Expected a Transform node");
+        };
+        assert!(matches!(source.view(), ChunkNode::Collect(_)));
+
+        // Versions sharing a prefix of the chain are independent
+        let doubled = chunk.transform(|x| x * 2);
+        let plus_one = doubled.clone().transform(|x| x + 1);
+        let squared = doubled.clone().transform(|x| x * x);
+        assert_eq!(doubled.as_vec(), vec![2, 4, 6]);
+        assert_eq!(plus_one.as_vec(), vec![3, 5, 7]);
+        assert_eq!(squared.as_vec(), vec![4, 16, 36]);
+
+        // Long chains are evaluated without recursion
+        let mut long = Chunk::new(0);
+        for _ in 0..100_000 {
+            long = long.transform(|x| x + 1);
+        }
+        assert_eq!(long.as_vec(), vec![100_000]);
+    }
+
     #[test]
     fn test_transform_flatten() {
         // Test transform_flatten on empty chunk
@@ -596,15 +2352,864 @@ mod tests {
         let collected: Chunk<i32> = vec![1, 2, 3].into_iter().collect();
 
         // Concat a single element
-        let result = collected.concat(Chunk::Single(4));
+        let result = collected.concat(Chunk::new(4));
 
         // Verify the result
         assert_eq!(result.as_vec(), vec![1, 2, 3, 4]);
 
         // Verify it's still a Collect variant (not a Concat)
-        match result {
-            Chunk::Collect(_) => (), // This is what we want
+        match result.view() {
+            ChunkNode::Collect(_) => (), // This is what we want
             _ => panic!("Expected Collect variant after optimization"),
         }
     }
+
+    #[test]
+    fn test_view() {
+        assert!(matches!(Chunk::<i32>::default().view(), ChunkNode::Empty));
+        assert!(matches!(Chunk::new(1).view(), ChunkNode::Single(1)));
+        assert!(matches!(
+            Chunk::new(1).append(2).view(),
+            ChunkNode::Collect([1, 2])
+        ));
+
+        let chunk = Chunk::new(1).transform(|x| x + 1).append(3);
+        let ChunkNode::Concat(a, b) = chunk.view() else {
+            panic!("Expected a Concat node");
+        };
+        let ChunkNode::Transform(source) = a.view() else {
+            panic!("Expected a Transform node");
+        };
+        assert_eq!(source.as_vec(), vec![1]);
+        assert!(matches!(b.view(), ChunkNode::Single(3)));
+
+        assert!(matches!(
+            Chunk::new(1).map(|x| x.to_string()).view(),
+            ChunkNode::Map
+        ));
+        assert!(matches!(
+            Chunk::new(1).map(|x| x + 1).view(),
+            ChunkNode::Transform(_)
+        ));
+    }
+
+    #[test]
+    fn test_reverse() {
+        let source: Chunk<_> = (1..=6).collect();
+        let chunks = vec![
+            Chunk::default(),
+            Chunk::new(1),
+            Chunk::new(0).concat(source.clone()).append(7),
+            source.clone().transform(|x| x * 10),
+            source.clone().filter(|x| x % 2 == 0),
+            source.clone().take_while(|x| *x < 4).transform(|x| x + 1),
+            source.clone().skip_while(|x| *x < 4),
+            source
+                .clone()
+                .transform_flatten(|x| Chunk::new(x).append(x * 10))
+                .take_while(|x| *x < 30),
+            source.clone().map(|x| x - 1).prepend(9),
+            source.clone().transform_memo(|x| x * 2),
+            source.clone().reverse().append(0).prepend(7),
+        ];
+
+        for chunk in chunks {
+            let mut expected = chunk.as_vec();
+            expected.reverse();
+            let reversed = chunk.clone().reverse();
+            assert_eq!(reversed.as_vec(), expected);
+            assert_eq!(reversed.clone().into_vec(), expected);
+            assert_eq!(reversed.size_hint(), chunk.size_hint());
+            assert_eq!(reversed.first(), chunk.last());
+            assert_eq!(reversed.last(), chunk.first());
+            for i in 0..=expected.len() {
+                assert_eq!(reversed.get(i).as_deref(), expected.get(i));
+            }
+            assert_eq!(reversed.reverse().as_vec(), chunk.as_vec());
+        }
+
+        // Uniquely owned elements are still moved out of a reversed chunk
+        let strings = Chunk::new(String::from("a")).append(String::from("b"));
+        assert_eq!(strings.reverse().try_into_vec().unwrap(), vec!["b", "a"]);
+    }
+
+    #[test]
+    fn test_reverse_is_lazy() {
+        let chunk = Chunk::new(1).append(2).transform(|x| x + 1);
+        let reversed = chunk.clone().reverse();
+        let ChunkNode::Reverse(source) = reversed.view() else {
+            panic!("Expected a Reverse node");
+        };
+        assert!(matches!(source.view(), ChunkNode::Transform(_)));
+        assert!(matches!(reversed.reverse().view(), ChunkNode::Transform(_)));
+
+        // Reversing a reversed chunk that is shared unwraps it as well
+        let reversed = chunk.clone().reverse();
+        let shared = reversed.clone();
+        let unwrapped = reversed.reverse();
+        assert!(matches!(unwrapped.view(), ChunkNode::Transform(_)));
+        assert_eq!(unwrapped.as_vec(), chunk.as_vec());
+        assert_eq!(shared.as_vec(), vec![3, 2]);
+
+        // So round trips through shared reversals don't make the chunk any deeper
+        let mut round_trip = chunk.clone();
+        let mut versions = Vec::new();
+        for _ in 0..100 {
+            let reversed = round_trip.reverse();
+            versions.push(reversed.clone());
+            round_trip = reversed.reverse();
+        }
+        assert!(matches!(round_trip.view(), ChunkNode::Transform(a) if a.len() == Some(2)));
+        let ChunkNode::Reverse(source) = versions[99].view() else {
+            panic!("Expected a Reverse node");
+        };
+        assert!(matches!(source.view(), ChunkNode::Transform(_)));
+        assert_eq!(round_trip.as_vec(), chunk.as_vec());
+    }
+
+    #[test]
+    fn test_uncons_unsnoc() {
+        let source: Chunk<_> = (1..=6).collect();
+        let chunks = vec![
+            Chunk::default(),
+            Chunk::new(1),
+            Chunk::new(0).concat(source.clone()).append(7).prepend(-1),
+            source.clone().transform(|x| x * 10).concat(source.clone()),
+            source.clone().filter(|x| x % 2 == 0).prepend(0),
+            source
+                .clone()
+                .transform_flatten(|x| Chunk::new(x).append(x * 10))
+                .take_while(|x| *x < 30),
+            source.clone().map(|x| x - 1).append(9),
+            source.clone().transform_memo(|x| x * 2),
+            source.clone().reverse().append(0).prepend(7).reverse(),
+            source.clone().filter(|_| false).concat(source.clone()),
+        ];
+
+        for chunk in chunks {
+            let expected = chunk.as_vec();
+
+            // Splitting off the front one element at a time
+            let mut front = Vec::new();
+            let mut rest = chunk.clone();
+            while let Some((a, tail)) = rest.uncons() {
+                front.push(a);
+                assert_eq!(tail.as_vec(), expected[front.len()..]);
+                rest = tail;
+            }
+            assert_eq!(front, expected);
+
+            // Splitting off the back one element at a time
+            let mut back = Vec::new();
+            let mut rest = chunk.clone();
+            while let Some((init, a)) = rest.unsnoc() {
+                back.push(a);
+                assert_eq!(init.as_vec(), expected[..expected.len() - back.len()]);
+                rest = init;
+            }
+            back.reverse();
+            assert_eq!(back, expected);
+
+            // Alternating between both ends
+            let mut deque: std::collections::VecDeque<_> = expected.iter().copied().collect();
+            let mut rest = chunk.clone();
+            for i in 0.. {
+                let (a, b) = if i % 2 == 0 {
+                    (rest.pop_front(), deque.pop_front())
+                } else {
+                    (rest.pop_back(), deque.pop_back())
+                };
+                assert_eq!(a, b);
+                if a.is_none() {
+                    break;
+                }
+            }
+
+            assert_eq!(chunk.as_vec(), expected);
+        }
+
+        // The remaining elements of a collection are shared rather than copied
+        let (_, rest) = Chunk::from(vec![1, 2, 3]).uncons().unwrap();
+        assert!(matches!(rest.view(), ChunkNode::Collect([2, 3])));
+    }
+
+    #[test]
+    fn test_pop_amortized() {
+        const LEN: usize = 100_000;
+
+        // A left-leaning chain of shared appends, emptied from the front
+        let mut queue = Chunk::default();
+        let mut versions = Vec::new();
+        for i in 0..LEN {
+            versions.push(queue.clone());
+            queue = queue.append(i);
+        }
+        for i in 0..LEN {
+            assert_eq!(queue.pop_front(), Some(i));
+        }
+        assert!(queue.is_null());
+        assert_eq!(versions[LEN - 1].len(), Some(LEN - 1));
+
+        // A right-leaning chain of prepends, emptied from the back
+        let mut stack = (0..LEN)
+            .rev()
+            .fold(Chunk::default(), |chunk, i| chunk.prepend(i));
+        for i in (0..LEN).rev() {
+            assert_eq!(stack.pop_back(), Some(i));
+        }
+        assert_eq!(stack.pop_back(), None);
+
+        // A queue where elements are added at the back while others are taken off the front
+        let mut queue = Chunk::default();
+        let mut taken = Vec::new();
+        for i in 0..LEN {
+            queue = queue.append(i);
+            if i % 2 == 1 {
+                taken.extend(queue.pop_front());
+            }
+        }
+        taken.extend(core::iter::from_fn(|| queue.pop_front()));
+        assert_eq!(taken, (0..LEN).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_as_vec_deep_chunks() {
+        const DEPTH: usize = 1_000_000;
+
+        // A right-leaning chain of a million `Concat` nodes
+        let mut prepended = Chunk::default();
+        for i in (0..DEPTH).rev() {
+            prepended = prepended.prepend(i);
+        }
+        let vec = prepended.as_vec();
+        assert_eq!(vec.len(), DEPTH);
+        assert!(vec.iter().enumerate().all(|(i, a)| *a == i));
+
+        // A left-leaning chain, where sharing defeats the in-place `Collect` push
+        let mut appended = Chunk::default();
+        let mut versions = Vec::new();
+        for i in 0..DEPTH {
+            versions.push(appended.clone());
+            appended = appended.append(i);
+        }
+        drop(versions);
+        let vec = appended.as_vec();
+        assert_eq!(vec.len(), DEPTH);
+        assert!(vec.iter().enumerate().all(|(i, a)| *a == i));
+
+        // A million nested reversals, each followed by an append
+        let mut reversed = Chunk::default();
+        for i in 0..DEPTH {
+            reversed = reversed.reverse().append(i);
+        }
+        let vec = reversed.as_vec();
+        assert_eq!(vec.len(), DEPTH);
+        assert_eq!(vec[..3], [DEPTH - 2, DEPTH - 4, DEPTH - 6]);
+        assert_eq!(vec[DEPTH - 2..], [DEPTH - 3, DEPTH - 1]);
+
+        // A million nested transformations
+        let mut transformed = Chunk::new(0);
+        for _ in 0..DEPTH {
+            transformed = transformed.transform(|x| x + 1);
+        }
+        assert_eq!(transformed.as_vec(), vec![DEPTH]);
+
+        // A hundred thousand nested filters, which are not fused
+        let mut filtered = Chunk::new(0);
+        for _ in 0..100_000 {
+            filtered = filtered.filter(|_| true);
+        }
+        assert_eq!(filtered.as_vec(), vec![0]);
+        assert_eq!(filtered.iter().next().as_deref(), Some(&0));
+    }
+
+    #[test]
+    fn test_as_vec_streams_transformations() {
+        use std::cell::RefCell;
+
+        // Each element goes through both flattenings before the next one is read
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let (outer, inner) = (seen.clone(), seen.clone());
+        let chunk = Chunk::default()
+            .append(1)
+            .append(2)
+            .transform_flatten(move |x| {
+                outer.borrow_mut().push(x);
+                Chunk::new(x).append(x * 10)
+            })
+            .transform_flatten(move |x| {
+                inner.borrow_mut().push(-x);
+                Chunk::new(x)
+            });
+        assert_eq!(chunk.as_vec(), vec![1, 10, 2, 20]);
+        assert_eq!(*seen.borrow(), vec![1, -1, -10, 2, -2, -20]);
+
+        // Operations after a flattening still apply to the elements it produces
+        let chunk: Chunk<_> = (0..4).collect();
+        let chunk = chunk
+            .transform_flatten(|x| Chunk::new(x).append(x))
+            .filter(|x| x % 2 == 0)
+            .take_while(|x| *x < 2)
+            .transform(|x| x + 1);
+        assert_eq!(chunk.as_vec(), vec![1, 1]);
+    }
+
+    #[test]
+    fn test_drop_deep_chunks() {
+        use std::cell::Cell;
+
+        #[derive(Clone)]
+        struct Counted<'a>(&'a Cell<usize>);
+        impl Drop for Counted<'_> {
+            fn drop(&mut self) {
+                self.0.set(self.0.get() + 1);
+            }
+        }
+
+        const DEPTH: usize = 1_000_000;
+        let dropped = Cell::new(0);
+
+        let mut chunk = Chunk::default();
+        for _ in 0..DEPTH {
+            chunk = chunk.prepend(Counted(&dropped));
+        }
+        let shared = chunk.clone().prepend(Counted(&dropped));
+
+        // Dropping one version keeps the nodes shared with the other alive
+        drop(chunk);
+        assert_eq!(dropped.get(), 0);
+
+        drop(shared);
+        assert_eq!(dropped.get(), DEPTH + 1);
+
+        // Deeply nested transformations are released iteratively as well
+        let mut transformed = Chunk::new(0);
+        for _ in 0..DEPTH {
+            transformed = transformed.transform(|x| x + 1);
+        }
+        drop(transformed);
+    }
+
+    #[test]
+    fn test_len() {
+        let empty: Chunk<i32> = Chunk::default();
+        assert_eq!(empty.len(), Some(0));
+
+        let chunk = Chunk::default().append(1).append(2).prepend(0);
+        assert_eq!(chunk.len(), Some(3));
+
+        // Shared versions are counted through the cached link sizes
+        let shared = chunk.clone().concat(chunk.clone()).append(3);
+        assert_eq!(shared.len(), Some(7));
+        assert_eq!(shared.len(), Some(shared.as_vec().len()));
+
+        // Empty collections do not count
+        let empties = Chunk::<i32>::from_iter(vec![]).concat(Chunk::from_iter(vec![]));
+        assert_eq!(empties.len(), Some(0));
+        assert!(empties.is_null());
+    }
+
+    #[test]
+    fn test_size_hint() {
+        let chunk: Chunk<_> = (0..5).collect();
+        assert_eq!(chunk.size_hint(), (5, Some(5)));
+
+        let transformed = chunk.clone().transform(|x| x + 1);
+        assert_eq!(transformed.size_hint(), (5, Some(5)));
+
+        let flattened = chunk.clone().transform_flatten(Chunk::new);
+        assert_eq!(flattened.size_hint(), (0, None));
+        assert_eq!(flattened.len(), None);
+
+        let mixed = chunk.clone().concat(flattened).append(1);
+        assert_eq!(mixed.size_hint(), (6, None));
+
+        // Doubling a shared chunk overflows the upper bound long before memory runs out
+        let mut doubled = chunk;
+        for _ in 0..70 {
+            doubled = doubled.clone().concat(doubled);
+        }
+        assert_eq!(doubled.size_hint(), (usize::MAX, None));
+    }
+
+    #[test]
+    fn test_get() {
+        let chunk = Chunk::default()
+            .append(1)
+            .append(2)
+            .prepend(0)
+            .concat((3..6).collect());
+        let vec = chunk.as_vec();
+        for (i, a) in vec.iter().enumerate() {
+            assert_eq!(chunk.get(i).as_deref(), Some(a));
+        }
+        assert_eq!(chunk.get(vec.len()), None);
+
+        // Transformed subtrees on the path are evaluated
+        let flattened = chunk
+            .clone()
+            .transform_flatten(|x| {
+                if x % 2 == 0 {
+                    Chunk::new(x)
+                } else {
+                    Chunk::default()
+                }
+            })
+            .concat(chunk.clone())
+            .append(6);
+        let vec = flattened.as_vec();
+        for (i, a) in vec.iter().enumerate() {
+            assert_eq!(flattened.get(i).as_deref(), Some(a));
+        }
+        assert_eq!(flattened.get(vec.len()), None);
+    }
+
+    #[test]
+    fn test_get_only_evaluates_the_path() {
+        use std::cell::Cell;
+
+        let calls = Rc::new(Cell::new(0));
+        let counter = calls.clone();
+        let lazy = (0..100).collect::<Chunk<_>>().transform(move |x| {
+            counter.set(counter.get() + 1);
+            x
+        });
+        let chunk = (0..100)
+            .collect::<Chunk<_>>()
+            .concat(lazy.clone())
+            .concat(lazy);
+
+        assert_eq!(chunk.get(50).as_deref(), Some(&50));
+        assert_eq!(chunk.first().as_deref(), Some(&0));
+        assert_eq!(calls.get(), 0);
+
+        // Transforms keep the number of elements, so only the requested one is transformed
+        assert_eq!(chunk.get(102).as_deref(), Some(&2));
+        assert_eq!(calls.get(), 1);
+        assert_eq!(chunk.get(299).as_deref(), Some(&99));
+        assert_eq!(calls.get(), 2);
+        assert_eq!(chunk.last().as_deref(), Some(&99));
+        assert_eq!(calls.get(), 3);
+
+        // The same goes for maps to another element type, and for reversed chunks
+        let counter = calls.clone();
+        let mapped = (0..100).collect::<Chunk<_>>().map(move |x| {
+            counter.set(counter.get() + 1);
+            x as f64
+        });
+        assert_eq!(mapped.get(42).as_deref(), Some(&42.0));
+        assert_eq!(mapped.clone().reverse().first().as_deref(), Some(&99.0));
+        assert_eq!(calls.get(), 5);
+    }
+
+    #[test]
+    fn test_first_last() {
+        let empty: Chunk<i32> = Chunk::default();
+        assert_eq!(empty.first(), None);
+        assert_eq!(empty.last(), None);
+
+        let chunk = Chunk::default().append(1).append(2).prepend(0);
+        assert_eq!(chunk.first().as_deref(), Some(&0));
+        assert_eq!(chunk.last().as_deref(), Some(&2));
+
+        // A trailing subtree that turns out to be empty is skipped
+        let filtered = chunk
+            .clone()
+            .concat(chunk.clone().transform_flatten(|_| Chunk::default()));
+        assert_eq!(filtered.last().as_deref(), Some(&2));
+
+        let transformed = chunk.transform(|x| x * 10);
+        assert_eq!(transformed.first().as_deref(), Some(&0));
+        assert_eq!(transformed.last().as_deref(), Some(&20));
+
+        // Trailing elements that a filter drops are skipped from the end
+        let odd = transformed.concat((0..5).collect()).filter(|x| x % 2 == 1);
+        assert_eq!(odd.last().as_deref(), Some(&3));
+        assert_eq!(odd.reverse().first().as_deref(), Some(&3));
+    }
+
+    #[test]
+    fn test_concat_balanced() {
+        const N: usize = 10_000;
+        let max_depth = 2 * (usize::BITS - N.leading_zeros()) as usize;
+
+        // Repeated prepends
+        let mut prepended = Chunk::default();
+        for i in (0..N).rev() {
+            prepended = Chunk::new(i).concat_balanced(prepended);
+        }
+        assert!(prepended.depth() <= max_depth);
+        assert_eq!(prepended.as_vec(), (0..N).collect::<Vec<_>>());
+
+        // Folding concat over many small chunks
+        let folded = (0..N)
+            .map(|i| Chunk::default().append(i).transform_flatten(Chunk::new))
+            .fold(Chunk::default(), Chunk::concat_balanced);
+        assert!(folded.depth() <= max_depth);
+        assert_eq!(folded.len(), None);
+        assert_eq!(folded.as_vec(), (0..N).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_balance() {
+        let chunk = (0..1000).fold(Chunk::default(), |chunk, i| chunk.prepend(i));
+        assert_eq!(chunk.depth(), 998);
+
+        // Shared subtrees are reused without affecting the other versions
+        let shared = chunk.clone();
+        let balanced = chunk.balance();
+        assert!(balanced.depth() <= 20);
+        assert_eq!(balanced.len(), Some(1000));
+        assert_eq!(balanced.as_vec(), shared.as_vec());
+        assert_eq!(shared.depth(), 998);
+
+        // Balanced chunks are left alone
+        let small = Chunk::default().append(1).append(2).prepend(0);
+        assert_eq!(small.clone().balance().as_vec(), small.as_vec());
+    }
+
+    #[test]
+    fn test_map() {
+        let chunk = Chunk::default().append(1).append(2).prepend(0);
+        let mapped = chunk.clone().map(|x| format!("#{x}"));
+        assert_eq!(mapped.len(), Some(3));
+        assert_eq!(mapped.as_vec(), vec!["#0", "#1", "#2"]);
+        assert_eq!(mapped.get(1).as_deref().map(String::as_str), Some("#1"));
+        assert_eq!(mapped.last().as_deref().map(String::as_str), Some("#2"));
+
+        // Mapped chunks compose with the other operations
+        let combined = mapped
+            .append("#3".to_string())
+            .transform(|s| s.replace('#', "n"))
+            .map(|s| s.len());
+        assert_eq!(combined.as_vec(), vec![2, 2, 2, 2]);
+        assert_eq!(combined.clone().into_vec(), vec![2, 2, 2, 2]);
+        assert_eq!(chunk.as_vec(), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_map_is_lazy() {
+        let calls = Rc::new(std::cell::Cell::new(0));
+        let counter = calls.clone();
+        let chunk = (0..100).collect::<Chunk<_>>().map(move |x| {
+            counter.set(counter.get() + 1);
+            x as f64 / 2.0
+        });
+        assert_eq!(calls.get(), 0);
+
+        assert_eq!(chunk.first().as_deref(), Some(&0.0));
+        assert_eq!(chunk.iter().nth(3).as_deref(), Some(&1.5));
+        assert_eq!(calls.get(), 5);
+
+        // Walking it backwards evaluates one element at a time as well
+        calls.set(0);
+        assert_eq!(chunk.iter().next_back().as_deref(), Some(&49.5));
+        assert_eq!(chunk.last().as_deref(), Some(&49.5));
+        let reversed = chunk.clone().reverse();
+        assert_eq!(reversed.iter().nth(1).as_deref(), Some(&49.0));
+        assert_eq!(reversed.into_iter().next(), Some(49.5));
+        assert_eq!(calls.get(), 5);
+
+        let calls = Rc::new(std::cell::Cell::new(0));
+        let counter = calls.clone();
+        let flat = (0..100).collect::<Chunk<_>>().flat_map(move |x| {
+            counter.set(counter.get() + 1);
+            Chunk::new(x as f64).append(x as f64 / 2.0)
+        });
+        let reversed = flat.reverse();
+        assert_eq!(
+            reversed.iter().take(3).collect::<Vec<_>>(),
+            [49.5, 99.0, 49.0].map(Cow::<f64>::Owned)
+        );
+        assert_eq!(calls.get(), 2);
+    }
+
+    #[test]
+    fn test_map_deep_chains() {
+        const DEPTH: usize = 100_000;
+
+        // Maps that keep the element type are fused with each other
+        let mut mapped = Chunk::new(0);
+        for _ in 0..DEPTH {
+            mapped = mapped.map(|x| x + 1);
+        }
+        assert_eq!(mapped.as_vec(), vec![DEPTH]);
+        assert_eq!(mapped.iter().next().as_deref(), Some(&DEPTH));
+        drop(mapped);
+
+        let mut flat = Chunk::new(0);
+        for _ in 0..DEPTH {
+            flat = flat.flat_map(|x| Chunk::new(x + 1));
+        }
+        assert_eq!(flat.as_vec(), vec![DEPTH]);
+        drop(flat);
+
+        // Maps that change the element type are released iteratively
+        let mut alternating = Chunk::new(0);
+        for _ in 0..DEPTH / 2 {
+            alternating = alternating.map(i64::from).map(|x| x as i32);
+        }
+        let flat = alternating.clone().flat_map(|x| Chunk::new(x.to_string()));
+        drop(alternating);
+        drop(flat);
+    }
+
+    #[test]
+    fn test_flat_map() {
+        let chunk = Chunk::default().append(1).append(2).append(3);
+        let flat = chunk.flat_map(|x| (0..x).map(|i| (x, i)).collect());
+        assert_eq!(flat.size_hint(), (0, None));
+        assert_eq!(
+            flat.as_vec(),
+            vec![(1, 0), (2, 0), (2, 1), (3, 0), (3, 1), (3, 2)]
+        );
+
+        let nested = flat.flat_map(|(x, i)| Chunk::new(x * 10 + i).transform(|y| y + 1));
+        assert_eq!(nested.as_vec(), vec![11, 21, 22, 31, 32, 33]);
+
+        let empty = Chunk::<i32>::default().flat_map(|x| Chunk::new(x.to_string()));
+        assert!(empty.is_null());
+        assert_eq!(empty.as_vec(), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_try_transform() {
+        use std::cell::Cell;
+
+        let calls = Rc::new(Cell::new(0));
+        let counter = calls.clone();
+        let chunk: Chunk<_> = (1..=5).collect();
+        let checked = chunk.clone().try_transform(move |x| {
+            counter.set(counter.get() + 1);
+            if x % 2 == 0 {
+                Err(x)
+            } else {
+                Ok(x * 10)
+            }
+        });
+        assert_eq!(checked.len(), Some(5));
+
+        // Stops at the first error
+        assert_eq!(checked.try_as_vec(), Err(2));
+        assert_eq!(calls.get(), 2);
+
+        // Collects every error
+        assert_eq!(checked.try_as_vec_all().unwrap_err().as_vec(), vec![2, 4]);
+        assert_eq!(calls.get(), 7);
+
+        let valid = chunk.try_transform(|x| Ok::<_, ()>(x + 1));
+        assert_eq!(valid.try_as_vec(), Ok(vec![2, 3, 4, 5, 6]));
+        assert_eq!(valid.try_as_vec_all().unwrap(), vec![2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn test_try_transform_flatten() {
+        let chunk = Chunk::default().append(1).append(2).append(3);
+        let expanded = chunk.try_transform_flatten(|x| match x {
+            2 => Err("two"),
+            x => Ok(Chunk::default().append(x).append(x)),
+        });
+        assert_eq!(expanded.try_as_vec(), Err("two"));
+        assert_eq!(expanded.try_as_vec_all().unwrap_err().as_vec(), vec!["two"]);
+        assert_eq!(
+            expanded.as_vec(),
+            vec![Ok(1), Ok(1), Err("two"), Ok(3), Ok(3)]
+        );
+
+        let empty = Chunk::<i32>::default().try_transform_flatten(|_| Err::<Chunk<_>, ()>(()));
+        assert_eq!(empty.try_as_vec(), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_filter() {
+        let chunk: Chunk<_> = (1..=10).collect();
+        let evens = chunk.clone().filter(|x| x % 2 == 0);
+        assert_eq!(evens.size_hint(), (0, Some(10)));
+        assert_eq!(evens.as_vec(), vec![2, 4, 6, 8, 10]);
+        assert_eq!(evens.iter().map(|x| *x).collect::<Vec<_>>(), evens.as_vec());
+        assert_eq!(evens.clone().into_vec(), evens.as_vec());
+
+        // Matches the `transform_flatten` equivalent
+        let flattened = chunk.clone().transform_flatten(|x| {
+            if x % 3 == 0 {
+                Chunk::new(x * 10)
+            } else {
+                Chunk::default()
+            }
+        });
+        let filtered = chunk.filter_map(|x| if x % 3 == 0 { Some(x * 10) } else { None });
+        assert_eq!(filtered.as_vec(), flattened.as_vec());
+        assert_eq!(filtered, flattened);
+    }
+
+    #[test]
+    fn test_take_skip_while() {
+        use std::cell::Cell;
+
+        let chunk = Chunk::from(vec![1, 2, 3])
+            .concat(Chunk::new(10))
+            .concat((0..5).collect());
+        let taken = chunk.clone().take_while(|x| *x < 5);
+        let skipped = chunk.clone().skip_while(|x| *x < 5);
+        assert_eq!(taken.as_vec(), vec![1, 2, 3]);
+        assert_eq!(skipped.as_vec(), vec![10, 0, 1, 2, 3, 4]);
+        for chunk in [&taken, &skipped] {
+            assert_eq!(chunk.iter().map(|x| *x).collect::<Vec<_>>(), chunk.as_vec());
+            assert_eq!(chunk.clone().into_vec(), chunk.as_vec());
+        }
+
+        // Every evaluation starts over, also when the node is reached more than once
+        let twice = taken.clone().concat(taken.clone());
+        assert_eq!(twice.as_vec(), vec![1, 2, 3, 1, 2, 3]);
+        assert_eq!(twice.iter().count(), 6);
+
+        // The rest of the source is skipped once an element doesn't match
+        let calls = Rc::new(Cell::new(0));
+        let counter = calls.clone();
+        let counted = Chunk::new(1)
+            .append(2)
+            .concat(Chunk::new(3).inspect(move |_| counter.set(counter.get() + 1)))
+            .take_while(|x| *x < 2);
+        assert_eq!(counted.iter().count(), 1);
+        assert_eq!(calls.get(), 0);
+
+        // Operations below the node stop running as well, including fused ones
+        let source: Chunk<_> = (0..1000).collect();
+        let calls = Rc::new(Cell::new(0));
+        let count = |calls: &Rc<Cell<usize>>| {
+            let counter = calls.clone();
+            move || counter.set(counter.get() + 1)
+        };
+        let (on_map, on_filter, on_flatten) = (count(&calls), count(&calls), count(&calls));
+        let chunks = [
+            source.clone().transform(move |x| {
+                on_map();
+                x
+            }),
+            source.clone().filter(move |_| {
+                on_filter();
+                true
+            }),
+            source.clone().transform_flatten(move |x| {
+                on_flatten();
+                Chunk::new(x)
+            }),
+        ];
+        for chunk in chunks {
+            let taken = chunk.take_while(|x| *x < 5);
+            for evaluate in [
+                |chunk: &Chunk<i32>| chunk.as_vec(),
+                |chunk: &Chunk<i32>| chunk.iter().map(|x| *x).collect(),
+                |chunk: &Chunk<i32>| chunk.clone().into_vec(),
+            ] {
+                calls.set(0);
+                assert_eq!(evaluate(&taken), vec![0, 1, 2, 3, 4]);
+                assert_eq!(calls.get(), 6);
+            }
+        }
+    }
+
+    #[test]
+    fn test_inspect() {
+        use std::cell::RefCell;
+
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let log = seen.clone();
+        let chunk = Chunk::default()
+            .append(1)
+            .append(2)
+            .inspect(move |x| log.borrow_mut().push(*x))
+            .transform(|x| x * 10);
+        assert_eq!(chunk.len(), Some(2));
+        assert!(matches!(chunk.view(), ChunkNode::Transform(_)));
+        assert_eq!(chunk.first().as_deref(), Some(&10));
+        assert_eq!(*seen.borrow(), vec![1]);
+        assert_eq!(chunk.as_vec(), vec![10, 20]);
+        assert_eq!(*seen.borrow(), vec![1, 1, 2]);
+
+        // The size is known, so the chunk is walked backwards without being buffered
+        assert_eq!(chunk.iter().next_back().as_deref(), Some(&20));
+        assert_eq!(*seen.borrow(), vec![1, 1, 2, 2]);
+    }
+
+    #[test]
+    fn test_transform_memo() {
+        let calls = Rc::new(std::cell::Cell::new(0));
+        let counter = calls.clone();
+        let chunk = (1..=3).collect::<Chunk<_>>().transform_memo(move |x| {
+            counter.set(counter.get() + 1);
+            x * 10
+        });
+        assert_eq!(calls.get(), 0);
+
+        // Every way of reading the chunk shares the same evaluation
+        assert_eq!(chunk.get(1).as_deref(), Some(&20));
+        assert_eq!(calls.get(), 3);
+        assert_eq!(chunk.len(), Some(3));
+        assert_eq!(chunk.as_vec(), vec![10, 20, 30]);
+        assert_eq!(chunk.iter().map(|a| *a).sum::<i32>(), 60);
+        assert_eq!(chunk.last().as_deref(), Some(&30));
+
+        // Clones and chunks built on top of it reuse the cache too
+        let extended = chunk.clone().prepend(0).transform(|x| x + 1);
+        assert_eq!(extended.as_vec(), vec![1, 11, 21, 31]);
+        assert_eq!(chunk.clone().into_vec(), vec![10, 20, 30]);
+        assert_eq!(calls.get(), 3);
+
+        // Plain transformations are evaluated on every read
+        let counter = calls.clone();
+        let plain = (1..=3).collect::<Chunk<_>>().transform(move |x| {
+            counter.set(counter.get() + 1);
+            x
+        });
+        plain.as_vec();
+        plain.as_vec();
+        assert_eq!(calls.get(), 9);
+    }
+
+    #[test]
+    fn test_transform_memo_deep_chains() {
+        const DEPTH: usize = 100_000;
+
+        let mut chunk: Chunk<_> = (0..3).collect();
+        for _ in 0..DEPTH {
+            chunk = chunk.transform_memo(|x| x + 1);
+        }
+        let expected: Vec<_> = (DEPTH..DEPTH + 3).collect();
+        assert_eq!(chunk.clone().into_vec(), expected);
+        assert_eq!(chunk.as_vec(), expected);
+
+        let mut flattened = Chunk::new(0);
+        for _ in 0..DEPTH {
+            flattened = flattened.transform_flatten_memo(|x| Chunk::new(x + 1));
+        }
+        assert_eq!(flattened.get(0).as_deref(), Some(&DEPTH));
+    }
+
+    #[test]
+    fn test_memoize() {
+        let chunk = Chunk::new(1)
+            .concat(Chunk::new(2).transform_flatten(|x| Chunk::default().append(x).append(x)));
+        let memo = chunk.memoize();
+        assert!(matches!(memo.view(), ChunkNode::Memo(_, None)));
+        assert_eq!(memo.size_hint(), (1, None));
+        assert_eq!(memo.as_vec(), vec![1, 2, 2]);
+        assert_eq!(memo.size_hint(), (3, Some(3)));
+        assert!(matches!(memo.view(), ChunkNode::Memo(_, Some([1, 2, 2]))));
+
+        // Nothing to cache in chunks without transformations
+        assert!(matches!(
+            Chunk::new(1).memoize().view(),
+            ChunkNode::Single(1)
+        ));
+
+        // Memoized elements can be moved out without `A: Clone`
+        struct Handle(i32);
+        let handles = Chunk::new(Handle(1))
+            .transform_flatten_memo(|h| Chunk::new(Handle(h.0 + 1)))
+            .try_into_vec()
+            .unwrap();
+        assert_eq!(handles.len(), 1);
+        assert_eq!(handles[0].0, 2);
+    }
 }
@@ -0,0 +1,330 @@
+//! Comparison and hashing of [`Chunk`]s by their sequence of elements.
+//!
+//! Two chunks are equal when they yield the same elements in the same order, no matter how
+//! their trees are shaped. The comparisons stream over both chunks side by side without
+//! materializing them, and skip subtrees that both chunks share.
+
+use alloc::{borrow::Cow, rc::Rc, vec, vec::Vec};
+use core::{
+    cmp::Ordering,
+    hash::{Hash, Hasher},
+};
+
+use crate::{chunk::Node, Chunk, Iter};
+
+/// One side of a [`Pairs`] traversal.
+struct Cursor<'a, A> {
+    /// The subtrees that still have to be visited, leftmost on top
+    stack: Vec<&'a Chunk<A>>,
+    /// The elements of the leaf node currently being visited
+    items: Option<Iter<'a, A>>,
+    /// How many elements of the leaf node are left, if its length is known
+    remaining: Option<usize>,
+}
+
+impl<'a, A: Clone> Cursor<'a, A> {
+    fn new(chunk: &'a Chunk<A>) -> Self {
+        Cursor {
+            stack: vec![chunk],
+            items: None,
+            remaining: None,
+        }
+    }
+
+    /// Returns the next element, descending into the next subtree if needed.
+    fn next(&mut self) -> Option<Cow<'a, A>> {
+        loop {
+            if let Some(items) = &mut self.items {
+                match items.next() {
+                    Some(a) => {
+                        self.remaining = self.remaining.map(|n| n - 1);
+                        return Some(a);
+                    }
+                    None => self.items = None,
+                }
+            }
+            let chunk = self.stack.pop()?;
+            match &chunk.node {
+                Node::Concat(a, b) => {
+                    self.stack.push(b);
+                    self.stack.push(a);
+                }
+                _ => {
+                    self.items = Some(chunk.iter());
+                    self.remaining = chunk.len();
+                }
+            }
+        }
+    }
+
+    /// Returns `true` if the cursor is between two subtrees rather than inside a leaf node.
+    ///
+    /// A leaf whose length is unknown is only left once it has run out of elements.
+    fn at_boundary(&mut self) -> bool {
+        if self.remaining == Some(0) {
+            self.items = None;
+        }
+        self.items.is_none()
+    }
+
+    /// Splits the next subtree into its children, if it is a `Concat` node.
+    fn expand(&mut self) -> bool {
+        match self.stack.last().map(|chunk| &chunk.node) {
+            Some(Node::Concat(a, b)) => {
+                self.stack.pop();
+                self.stack.push(b);
+                self.stack.push(a);
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Returns `true` if both nodes are known to hold the same elements because they share
+/// the same allocation.
+fn same_node<A>(a: &Chunk<A>, b: &Chunk<A>) -> bool {
+    match (&a.node, &b.node) {
+        (Node::Collect(a), Node::Collect(b)) => Rc::ptr_eq(a, b),
+        (Node::Slice(a, i), Node::Slice(b, j)) => Rc::ptr_eq(a, b) && i == j,
+        _ => core::ptr::eq(a, b),
+    }
+}
+
+/// Walks two chunks side by side, yielding their elements in pairs until both are
+/// exhausted. Subtrees that both chunks share at the same position are skipped.
+struct Pairs<'a, A> {
+    left: Cursor<'a, A>,
+    right: Cursor<'a, A>,
+}
+
+impl<'a, A: Clone> Pairs<'a, A> {
+    fn new(left: &'a Chunk<A>, right: &'a Chunk<A>) -> Self {
+        Pairs {
+            left: Cursor::new(left),
+            right: Cursor::new(right),
+        }
+    }
+}
+
+impl<'a, A: Clone> Iterator for Pairs<'a, A> {
+    type Item = (Option<Cow<'a, A>>, Option<Cow<'a, A>>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Both sides are at a node boundary, so try to line up a shared subtree
+        while self.left.at_boundary() && self.right.at_boundary() {
+            let (Some(a), Some(b)) = (self.left.stack.last(), self.right.stack.last()) else {
+                break;
+            };
+            if same_node(a, b) {
+                self.left.stack.pop();
+                self.right.stack.pop();
+                continue;
+            }
+            // Split the larger subtree first, as it may contain the other one
+            let (first, second) = if a.size_hint().0 >= b.size_hint().0 {
+                (&mut self.left, &mut self.right)
+            } else {
+                (&mut self.right, &mut self.left)
+            };
+            if !first.expand() && !second.expand() {
+                break;
+            }
+        }
+
+        match (self.left.next(), self.right.next()) {
+            (None, None) => None,
+            pair => Some(pair),
+        }
+    }
+}
+
+impl<A: PartialEq + Clone> PartialEq for Chunk<A> {
+    /// Compares the elements of both chunks in order.
+    ///
+    /// Subtrees shared by both chunks are assumed to be equal without comparing their
+    /// elements, as if equality were reflexive for `A`.
+    ///
+    /// # Examples
+    /// ```
+    /// use tailcall_chunk::Chunk;
+    ///
+    /// let appended = Chunk::default().append(1).append(2);
+    /// let collected: Chunk<_> = vec![1, 2].into_iter().collect();
+    /// assert_eq!(appended, collected);
+    /// assert_ne!(appended, collected.append(3));
+    /// ```
+    fn eq(&self, other: &Self) -> bool {
+        if let (Some(a), Some(b)) = (self.len(), other.len()) {
+            if a != b {
+                return false;
+            }
+        }
+        Pairs::new(self, other).all(|pair| match pair {
+            (Some(a), Some(b)) => a == b,
+            _ => false,
+        })
+    }
+}
+
+impl<A: Eq + Clone> Eq for Chunk<A> {}
+
+impl<A: PartialOrd + Clone> PartialOrd for Chunk<A> {
+    /// Compares the elements of both chunks [lexicographically](Ord#lexicographical-comparison).
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        for pair in Pairs::new(self, other) {
+            match pair {
+                (Some(a), Some(b)) => match a.partial_cmp(&b) {
+                    Some(Ordering::Equal) => {}
+                    ordering => return ordering,
+                },
+                (None, _) => return Some(Ordering::Less),
+                (_, None) => return Some(Ordering::Greater),
+            }
+        }
+        Some(Ordering::Equal)
+    }
+}
+
+impl<A: Ord + Clone> Ord for Chunk<A> {
+    /// Compares the elements of both chunks [lexicographically](Ord#lexicographical-comparison).
+    ///
+    /// # Examples
+    /// ```
+    /// use tailcall_chunk::Chunk;
+    ///
+    /// let a = Chunk::default().append(1).append(2);
+    /// let b = Chunk::default().append(1).append(3);
+    /// assert!(a < b);
+    /// assert!(a.clone().append(0) > a);
+    /// ```
+    fn cmp(&self, other: &Self) -> Ordering {
+        for pair in Pairs::new(self, other) {
+            match pair {
+                (Some(a), Some(b)) => match a.cmp(&b) {
+                    Ordering::Equal => {}
+                    ordering => return ordering,
+                },
+                (None, _) => return Ordering::Less,
+                (_, None) => return Ordering::Greater,
+            }
+        }
+        Ordering::Equal
+    }
+}
+
+impl<A: Hash + Clone> Hash for Chunk<A> {
+    /// Hashes the elements of the chunk in order, followed by their number, so that equal
+    /// chunks have equal hashes regardless of their shape.
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let mut len = 0usize;
+        for a in self.iter() {
+            a.hash(state);
+            len += 1;
+        }
+        state.write_usize(len);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, collections::HashSet};
+
+    use super::*;
+
+    #[test]
+    fn test_eq_ignores_shape() {
+        let appended = Chunk::default().append(1).append(2).append(3);
+        let prepended = Chunk::default().prepend(3).prepend(2).prepend(1);
+        let collected: Chunk<_> = (1..=3).collect();
+        let transformed = Chunk::default()
+            .append(0)
+            .append(1)
+            .append(2)
+            .transform(|x| x + 1);
+        let flattened = Chunk::new(1).transform_flatten(|x| (x..=3).collect());
+
+        for chunk in [&prepended, &collected, &transformed, &flattened] {
+            assert_eq!(&appended, chunk);
+        }
+        assert_ne!(appended, collected.clone().append(4));
+        assert_ne!(appended, Chunk::default().append(1).append(2).append(4));
+        assert_ne!(Chunk::default(), flattened);
+        assert_eq!(
+            Chunk::<i32>::default(),
+            Chunk::new(1).transform_flatten(|_| Chunk::default())
+        );
+    }
+
+    #[test]
+    fn test_eq_skips_shared_subtrees() {
+        thread_local!(static CALLS: Cell<usize> = const { Cell::new(0) });
+
+        #[derive(Clone)]
+        struct Counted(i32);
+
+        impl PartialEq for Counted {
+            fn eq(&self, other: &Self) -> bool {
+                CALLS.with(|calls| calls.set(calls.get() + 1));
+                self.0 == other.0
+            }
+        }
+
+        let base: Chunk<_> = (0..1000).map(Counted).collect();
+        let a = base.clone().append(Counted(1));
+        let b = base.clone().append(Counted(1));
+        assert!(a == b);
+        assert_eq!(CALLS.with(Cell::get), 1);
+
+        let shared = a.clone().concat(base.clone());
+        assert!(shared.clone().prepend(Counted(0)) == shared.prepend(Counted(0)));
+        assert_eq!(CALLS.with(Cell::get), 2);
+    }
+
+    #[test]
+    fn test_eq_deep_trees() {
+        const LEN: i32 = 100_000;
+
+        // Appending to shared chunks builds a tree that leans left, so walking the leaf
+        // above it keeps a frame for each of its levels
+        let mut appended = Chunk::default();
+        for i in 0..LEN {
+            appended = appended.clone().append(i);
+        }
+        let transformed = appended.transform(|x| x);
+        let collected: Chunk<_> = (0..LEN).collect();
+        assert_eq!(transformed, collected);
+        assert_eq!(collected, transformed);
+        assert_eq!(transformed.cmp(&collected), Ordering::Equal);
+        assert!(transformed < collected.append(0));
+    }
+
+    #[test]
+    fn test_ord() {
+        let a: Chunk<_> = vec![1, 2, 3].into_iter().collect();
+        let b = Chunk::default().append(1).append(2).append(4);
+        let c = Chunk::default().append(1).append(2);
+
+        assert_eq!(a.cmp(&a.clone()), Ordering::Equal);
+        assert!(a < b);
+        assert!(c < a);
+        assert!(Chunk::default() < c);
+        assert_eq!(
+            a.partial_cmp(&c.clone().append(2).transform(|x| x)),
+            Some(Ordering::Greater)
+        );
+
+        let mut chunks = vec![b.clone(), a.clone(), c.clone()];
+        chunks.sort();
+        assert_eq!(chunks, vec![c, a, b]);
+    }
+
+    #[test]
+    fn test_hash() {
+        let mut set = HashSet::new();
+        set.insert(Chunk::default().append(1).append(2));
+        assert!(set.contains(&(1..=2).collect::<Chunk<_>>()));
+        assert!(set.contains(&Chunk::new(2).prepend(1).transform(|x| x)));
+        assert!(!set.contains(&Chunk::new(1)));
+    }
+}
@@ -0,0 +1,216 @@
+//! Conversions between [`Chunk`] and the standard collections.
+
+use alloc::{boxed::Box, collections::VecDeque, rc::Rc, vec::Vec};
+
+use crate::{chunk::Node, Chunk};
+
+impl<A> Extend<A> for Chunk<A> {
+    /// Appends the elements of an iterator to the chunk.
+    ///
+    /// If the chunk is a `Collect` node that no other version shares, the elements are
+    /// pushed into its vector in place. Otherwise they are collected into a new `Collect`
+    /// node that is concatenated to the chunk.
+    ///
+    /// # Examples
+    /// ```
+    /// use tailcall_chunk::Chunk;
+    ///
+    /// let mut chunk = Chunk::from(vec![1, 2]);
+    /// chunk.extend(3..5);
+    /// assert_eq!(chunk.as_vec(), vec![1, 2, 3, 4]);
+    /// ```
+    fn extend<T: IntoIterator<Item = A>>(&mut self, iter: T) {
+        let iter = iter.into_iter();
+        match &mut self.node {
+            Node::Collect(vec) => {
+                if let Some(vec) = Rc::get_mut(vec) {
+                    // No other version can observe the vector, so it is safe to push in place
+                    vec.extend(iter);
+                    return;
+                }
+            }
+            Node::Empty => {
+                *self = iter.collect();
+                return;
+            }
+            _ => {}
+        }
+
+        let other: Chunk<A> = iter.collect();
+        if !other.is_null() {
+            *self = core::mem::take(self).concat(other);
+        }
+    }
+}
+
+impl<A> From<Vec<A>> for Chunk<A> {
+    /// Creates a `Collect` chunk that takes ownership of the vector, without copying it.
+    fn from(vec: Vec<A>) -> Self {
+        Node::Collect(Rc::new(vec)).into()
+    }
+}
+
+impl<A, const N: usize> From<[A; N]> for Chunk<A> {
+    /// Creates a `Collect` chunk from the elements of an array.
+    ///
+    /// # Examples
+    /// ```
+    /// use tailcall_chunk::Chunk;
+    ///
+    /// let chunk = Chunk::from([1, 2, 3]);
+    /// assert_eq!(chunk.as_vec(), vec![1, 2, 3]);
+    /// ```
+    fn from(array: [A; N]) -> Self {
+        Chunk::from(Vec::from(array))
+    }
+}
+
+impl<A: Clone> From<&[A]> for Chunk<A> {
+    /// Creates a `Collect` chunk from clones of the elements of a slice.
+    fn from(slice: &[A]) -> Self {
+        Chunk::from(slice.to_vec())
+    }
+}
+
+impl<A> From<Box<[A]>> for Chunk<A> {
+    /// Creates a `Collect` chunk from a boxed slice, reusing its allocation.
+    fn from(slice: Box<[A]>) -> Self {
+        Chunk::from(slice.into_vec())
+    }
+}
+
+impl<A> From<Option<A>> for Chunk<A> {
+    /// Creates a chunk with the element of `Some`, or an empty chunk for `None`.
+    ///
+    /// # Examples
+    /// ```
+    /// use tailcall_chunk::Chunk;
+    ///
+    /// assert_eq!(Chunk::from(Some(1)).as_vec(), vec![1]);
+    /// assert!(Chunk::<i32>::from(None).is_null());
+    /// ```
+    fn from(option: Option<A>) -> Self {
+        match option {
+            Some(a) => Chunk::new(a),
+            None => Chunk::default(),
+        }
+    }
+}
+
+impl<A: Clone> From<Chunk<A>> for Vec<A> {
+    /// Converts the chunk into a vector, see [`Chunk::into_vec`].
+    fn from(chunk: Chunk<A>) -> Self {
+        chunk.into_vec()
+    }
+}
+
+impl<A: Clone> From<Chunk<A>> for VecDeque<A> {
+    /// Converts the chunk into a double-ended queue, see [`Chunk::into_vec`].
+    fn from(chunk: Chunk<A>) -> Self {
+        VecDeque::from(chunk.into_vec())
+    }
+}
+
+impl<A: Clone> From<Chunk<A>> for Box<[A]> {
+    /// Converts the chunk into a boxed slice, see [`Chunk::into_vec`].
+    fn from(chunk: Chunk<A>) -> Self {
+        chunk.into_vec().into_boxed_slice()
+    }
+}
+
+impl<A: Clone> From<Chunk<A>> for Rc<[A]> {
+    /// Converts the chunk into a reference counted slice, see [`Chunk::into_vec`].
+    fn from(chunk: Chunk<A>) -> Self {
+        Rc::from(chunk.into_vec())
+    }
+}
+
+/// Creates a [`Chunk`] containing the arguments, stored in a single `Collect` node.
+///
+/// Like [`vec!`], it accepts either a list of elements or an element and a number of
+/// repetitions.
+///
+/// # Examples
+/// ```
+/// use tailcall_chunk::{chunk, ChunkNode};
+///
+/// let chunk = chunk![1, 2, 3];
+/// assert!(matches!(chunk.view(), ChunkNode::Collect(_)));
+/// assert_eq!(chunk.as_vec(), vec![1, 2, 3]);
+///
+/// assert_eq!(chunk!["a"; 2].as_vec(), vec!["a", "a"]);
+/// ```
+#[macro_export]
+macro_rules! chunk {
+    () => {
+        $crate::Chunk::from($crate::__private::Vec::new())
+    };
+    ($elem:expr; $n:expr) => {
+        $crate::Chunk::from($crate::__private::vec![$elem; $n])
+    };
+    ($($x:expr),+ $(,)?) => {
+        $crate::Chunk::from($crate::__private::vec![$($x),+])
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ChunkNode;
+
+    #[test]
+    fn test_extend() {
+        // Pushes in place into a unique `Collect`
+        let mut chunk = Chunk::from(vec![1, 2]);
+        chunk.extend(vec![3, 4]);
+        assert!(matches!(chunk.view(), ChunkNode::Collect(_)));
+        assert_eq!(chunk.as_vec(), vec![1, 2, 3, 4]);
+
+        // Leaves shared versions untouched
+        let version = chunk.clone();
+        chunk.extend(Some(5));
+        assert_eq!(chunk.as_vec(), vec![1, 2, 3, 4, 5]);
+        assert_eq!(version.as_vec(), vec![1, 2, 3, 4]);
+
+        let mut empty = Chunk::default();
+        empty.extend(0..2);
+        assert!(matches!(empty.view(), ChunkNode::Collect(_)));
+
+        let mut transformed = Chunk::new(1).transform(|x| x * 10);
+        transformed.extend(None);
+        transformed.extend([2, 3]);
+        assert_eq!(transformed.as_vec(), vec![10, 2, 3]);
+    }
+
+    #[test]
+    fn test_from() {
+        let expected = vec![1, 2, 3];
+        assert_eq!(Chunk::from(vec![1, 2, 3]).as_vec(), expected);
+        assert_eq!(Chunk::from([1, 2, 3]).as_vec(), expected);
+        assert_eq!(Chunk::from(&expected[..]).as_vec(), expected);
+        assert_eq!(
+            Chunk::from(expected.clone().into_boxed_slice()).as_vec(),
+            expected
+        );
+        assert_eq!(Chunk::from(Some(1)).as_vec(), vec![1]);
+        assert!(Chunk::<i32>::from(None).is_null());
+    }
+
+    #[test]
+    fn test_into() {
+        let chunk = Chunk::default().append(1).append(2).prepend(0);
+        assert_eq!(Vec::from(chunk.clone()), vec![0, 1, 2]);
+        assert_eq!(VecDeque::from(chunk.clone()), VecDeque::from([0, 1, 2]));
+        assert_eq!(Box::<[_]>::from(chunk.clone()), Box::from([0, 1, 2]));
+        assert_eq!(Rc::<[_]>::from(chunk), Rc::from([0, 1, 2]));
+    }
+
+    #[test]
+    fn test_chunk_macro() {
+        let empty: Chunk<i32> = chunk![];
+        assert!(empty.is_null());
+        assert_eq!(chunk![1, 2, 3,], Chunk::from([1, 2, 3]));
+        assert_eq!(chunk![0; 3].as_vec(), vec![0, 0, 0]);
+        assert!(matches!(chunk![1].view(), ChunkNode::Collect(_)));
+    }
+}
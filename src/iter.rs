@@ -0,0 +1,1077 @@
+//! Lazy iteration over the elements of a [`Chunk`].
+
+use alloc::{borrow::Cow, boxed::Box, collections::VecDeque, rc::Rc, vec, vec::Vec};
+use core::{
+    cell::Cell,
+    fmt,
+    iter::FusedIterator,
+    ops::{ControlFlow, Range},
+};
+
+use crate::{
+    chunk::{Mapped, MemoCache, Node, Op},
+    Chunk, Link, Sink,
+};
+
+/// The chain of `Transform` operations that the elements of a frame still have to go
+/// through, innermost first. `None` means the elements are yielded as they are.
+type Cont<A> = Option<Rc<Step<A>>>;
+
+struct Step<A> {
+    op: Op<A>,
+    next: Cont<A>,
+    /// Whether a `TakeWhile` or `SkipWhile` operation has seen its first non-matching
+    /// element. Every traversal of a `Transform` node gets its own step, and so its own flag.
+    done: Cell<bool>,
+    /// Whether the `Transform` node is walked backwards, and so the chunks produced by a
+    /// `Flatten` operation as well.
+    rev: bool,
+    /// The nearest `TakeWhile` step further along the chain, if any.
+    limit: Cont<A>,
+}
+
+impl<A> Step<A> {
+    fn push(op: Op<A>, next: Cont<A>, rev: bool) -> Cont<A> {
+        let limit = next.as_ref().and_then(|step| match step.op {
+            Op::TakeWhile(_) => Some(step.clone()),
+            _ => step.limit.clone(),
+        });
+        Some(Rc::new(Step {
+            op,
+            next,
+            done: Cell::new(false),
+            rev,
+            limit,
+        }))
+    }
+
+    /// Returns `true` if no more elements can make it through `cont`, because one of its
+    /// `TakeWhile` operations has stopped, so that the frames it applies to can be skipped.
+    ///
+    /// Only the `TakeWhile` steps of the chain are visited.
+    fn is_exhausted(cont: &Cont<A>) -> bool {
+        let mut step = cont.as_deref();
+        while let Some(current) = step {
+            if matches!(current.op, Op::TakeWhile(_)) && current.done.get() {
+                return true;
+            }
+            step = current.limit.as_deref();
+        }
+        false
+    }
+
+    /// Returns `true` if the elements of a frame can all be run through `cont` in one go,
+    /// as none of its operations flattens or stops the iteration.
+    fn is_uninterrupted(mut cont: &Cont<A>) -> bool {
+        while let Some(step) = cont {
+            if matches!(step.op, Op::Flatten(_) | Op::TakeWhile(_)) {
+                return false;
+            }
+            cont = &step.next;
+        }
+        true
+    }
+
+    /// Returns `true` if every element of a frame comes out of `cont` as exactly one
+    /// element, so that the frame keeps its size.
+    fn is_one_to_one(mut cont: &Cont<A>) -> bool {
+        while let Some(step) = cont {
+            if !matches!(step.op, Op::Map(_)) {
+                return false;
+            }
+            cont = &step.next;
+        }
+        true
+    }
+
+    /// Runs `a` through the operations of `cont`, up to the first `Flatten`, whose result
+    /// is pushed onto `owned` along with the rest of the chain.
+    ///
+    /// Returns the element if it made it through every operation.
+    fn run(mut a: A, mut cont: &Cont<A>, owned: &mut IntoIter<A>) -> Option<A> {
+        while let Some(step) = cont {
+            match &step.op {
+                Op::Map(fs) => a = Op::map_all(fs, a),
+                Op::Flatten(f) => {
+                    owned.push(f(a), step.next.clone(), step.rev);
+                    return None;
+                }
+                Op::FilterMap(f) => a = f(a)?,
+                Op::TakeWhile(p) => {
+                    if step.done.get() || !p(&a) {
+                        step.done.set(true);
+                        return None;
+                    }
+                }
+                Op::SkipWhile(p) => {
+                    if !step.done.get() {
+                        if p(&a) {
+                            return None;
+                        }
+                        step.done.set(true);
+                    }
+                }
+            }
+            cont = &step.next;
+        }
+        Some(a)
+    }
+}
+
+impl<A> Drop for Step<A> {
+    /// Releases the rest of the chain without recursing into it, as deep chunks can have a
+    /// step for every one of their `Transform` nodes.
+    fn drop(&mut self) {
+        let mut next = self.next.take();
+        while let Some(step) = next {
+            next = match Rc::try_unwrap(step) {
+                Ok(mut step) => step.next.take(),
+                // Still used by another frame, which releases it later
+                Err(_) => None,
+            };
+        }
+    }
+}
+
+/// A lazy iterator over the elements of a [`Chunk`].
+///
+/// This struct is created by [`Chunk::iter`]. Elements stored in `Single` and `Collect`
+/// nodes of the iterated chunk are yielded by reference as [`Cow::Borrowed`], while
+/// elements produced by pending transformations are computed on the fly and yielded as
+/// [`Cow::Owned`].
+///
+/// The traversal uses an explicit stack, so iterating a chunk of any depth is stack-safe,
+/// and nothing is evaluated beyond the elements that are actually consumed.
+///
+/// The iterator is double-ended. The elements are walked from both ends independently
+/// when their number is known upfront; otherwise, e.g. below a pending
+/// [`filter`](Chunk::filter), the first call to [`next_back`](DoubleEndedIterator::next_back)
+/// evaluates all the remaining elements.
+///
+/// # Examples
+/// ```
+/// use tailcall_chunk::Chunk;
+///
+/// let chunk = Chunk::default().append(1).append(2).append(3);
+/// let firsts: Vec<i32> = chunk.iter().take(2).map(|a| *a).collect();
+/// assert_eq!(firsts, vec![1, 2]);
+///
+/// let lasts: Vec<i32> = chunk.iter().rev().take(2).map(|a| *a).collect();
+/// assert_eq!(lasts, vec![3, 2]);
+/// ```
+pub struct Iter<'a, A> {
+    root: &'a Chunk<A>,
+    front: Walk<'a, A>,
+    /// The traversal from the back, started by the first call to `next_back`
+    back: Option<Back<'a, A>>,
+}
+
+enum Back<'a, A> {
+    /// A traversal of the chunk backwards, along with the number of elements left between
+    /// both ends
+    Walk(Walk<'a, A>, usize),
+    /// The remaining elements, evaluated at once since their number wasn't known
+    Buffered(VecDeque<A>),
+}
+
+/// A traversal of a chunk in one direction.
+struct Walk<'a, A> {
+    stack: Vec<(Frame<'a, A>, Cont<A>)>,
+    /// Chunks produced by transformations, which are always drained before `stack`
+    owned: IntoIter<A>,
+}
+
+enum Frame<'a, A> {
+    /// A node borrowed from the iterated chunk, walked backwards if the flag is set
+    Node(&'a Chunk<A>, bool),
+    /// The remaining elements of a borrowed `Collect` node, yielded backwards if the flag
+    /// is set
+    Slice(core::slice::Iter<'a, A>, bool),
+}
+
+impl<'a, A: Clone> Iter<'a, A> {
+    pub(crate) fn new(chunk: &'a Chunk<A>) -> Self {
+        Iter {
+            root: chunk,
+            front: Walk::new(chunk, false),
+            back: None,
+        }
+    }
+
+    /// Returns an iterator over the elements of `chunk` in reverse order.
+    ///
+    /// Unlike [`rev`](Iterator::rev), this never buffers the elements, as the front and back
+    /// of the chunk don't have to meet.
+    pub(crate) fn back(chunk: &'a Chunk<A>) -> impl Iterator<Item = Cow<'a, A>> {
+        let mut walk = Walk::new(chunk, true);
+        core::iter::from_fn(move || walk.next())
+    }
+
+    /// Sends the remaining elements to `sink`.
+    pub(crate) fn send_to(self, sink: &mut impl Sink<A>) -> ControlFlow<()> {
+        if self.back.is_none() {
+            return self.front.send_to(sink);
+        }
+        for a in self {
+            sink.accept(a.into_owned())?;
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+impl<'a, A: Clone> Walk<'a, A> {
+    fn new(chunk: &'a Chunk<A>, rev: bool) -> Self {
+        Walk {
+            stack: vec![(Frame::Node(chunk, rev), None)],
+            owned: IntoIter::empty(),
+        }
+    }
+
+    /// Sends the remaining elements to `sink`, handing over the untransformed elements of
+    /// `Collect` nodes a whole slice at a time.
+    fn send_to(mut self, sink: &mut impl Sink<A>) -> ControlFlow<()> {
+        loop {
+            if self.owned.stack.is_empty() {
+                if let Some((Frame::Slice(iter, false), cont)) = self.stack.last_mut() {
+                    match cont.as_deref() {
+                        None => sink.accept_all(iter.cloned())?,
+                        // A lone operation is applied directly, which optimizes much better
+                        Some(Step {
+                            op: Op::Map(fs),
+                            next: None,
+                            ..
+                        }) => sink.accept_all(iter.map(|a| Op::map_all(fs, a.clone())))?,
+                        Some(Step {
+                            op: Op::FilterMap(f),
+                            next: None,
+                            ..
+                        }) => sink.accept_all(iter.filter_map(|a| f(a.clone())))?,
+                        Some(_) if Step::is_uninterrupted(cont) => {
+                            let owned = &mut self.owned;
+                            sink.accept_all(iter.filter_map(|a| Step::run(a.clone(), cont, owned)))?
+                        }
+                        Some(_) => {
+                            // Runs the slice through its operations in a tight loop, until
+                            // a flattened chunk has to be drained first
+                            for a in iter.by_ref() {
+                                match Step::run(a.clone(), cont, &mut self.owned) {
+                                    Some(a) => sink.accept(a)?,
+                                    None if !self.owned.stack.is_empty()
+                                        || Step::is_exhausted(cont) =>
+                                    {
+                                        break
+                                    }
+                                    None => {}
+                                }
+                            }
+                        }
+                    }
+                    if iter.len() == 0 || Step::is_exhausted(cont) {
+                        self.stack.pop();
+                    }
+                    continue;
+                }
+            }
+            match self.next() {
+                Some(a) => sink.accept(a.into_owned())?,
+                None => return ControlFlow::Continue(()),
+            }
+        }
+    }
+
+    fn next(&mut self) -> Option<Cow<'a, A>> {
+        loop {
+            if !self.owned.stack.is_empty() {
+                if let Some(a) = self.owned.next() {
+                    return Some(Cow::Owned(a));
+                }
+            }
+
+            let (frame, cont) = self.stack.last_mut()?;
+            if Step::is_exhausted(cont) {
+                self.stack.pop();
+                continue;
+            }
+            let elem = match frame {
+                Frame::Slice(iter, rev) => {
+                    let next = if *rev { iter.next_back() } else { iter.next() };
+                    match next {
+                        Some(a) => (a, cont.clone()),
+                        None => {
+                            self.stack.pop();
+                            continue;
+                        }
+                    }
+                }
+                Frame::Node(chunk, rev) => {
+                    let (chunk, rev) = (*chunk, *rev);
+                    let (_, cont) = self.stack.pop()?;
+                    match &chunk.node {
+                        Node::Empty => continue,
+                        Node::Single(a) => (a, cont),
+                        Node::Collect(vec) => {
+                            self.stack.push((Frame::Slice(vec.iter(), rev), cont));
+                            continue;
+                        }
+                        Node::Slice(vec, range) => {
+                            let iter = vec[range.clone()].iter();
+                            self.stack.push((Frame::Slice(iter, rev), cont));
+                            continue;
+                        }
+                        Node::Concat(a, b) => {
+                            let (first, second) = if rev { (b, a) } else { (a, b) };
+                            self.stack.push((Frame::Node(second, rev), cont.clone()));
+                            self.stack.push((Frame::Node(first, rev), cont));
+                            continue;
+                        }
+                        Node::Transform(_, op) if rev && op.is_order_dependent() => {
+                            self.owned.push(chunk.as_vec().into(), cont, true);
+                            continue;
+                        }
+                        Node::Transform(a, op) => {
+                            let cont = Step::push(op.clone(), cont, rev);
+                            self.stack.push((Frame::Node(a, rev), cont));
+                            continue;
+                        }
+                        Node::Map(mapped) => {
+                            self.owned.push(Node::Map(mapped.clone()).into(), cont, rev);
+                            continue;
+                        }
+                        Node::Memo(a, cell) => {
+                            let vec = Chunk::memoized(a, &**cell);
+                            self.stack.push((Frame::Slice(vec.iter(), rev), cont));
+                            continue;
+                        }
+                        Node::Reverse(a) => {
+                            self.stack.push((Frame::Node(a, !rev), cont));
+                            continue;
+                        }
+                    }
+                }
+            };
+
+            match elem {
+                (a, None) => return Some(Cow::Borrowed(a)),
+                (a, cont) => {
+                    if let Some(a) = Step::run(a.clone(), &cont, &mut self.owned) {
+                        return Some(Cow::Owned(a));
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<A> Walk<'_, A> {
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let hints = self.stack.iter().map(|(frame, cont)| match frame {
+            _ if !Step::is_one_to_one(cont) => (0, None),
+            Frame::Node(chunk, _) => chunk.size_hint(),
+            Frame::Slice(iter, _) => (iter.len(), Some(iter.len())),
+        });
+        sum_hints(hints.chain(core::iter::once(self.owned.size_hint())))
+    }
+}
+
+impl<'a, A: Clone> Iterator for Iter<'a, A> {
+    type Item = Cow<'a, A>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.back {
+            None => self.front.next(),
+            Some(Back::Walk(_, 0)) => None,
+            Some(Back::Walk(_, remaining)) => {
+                *remaining -= 1;
+                self.front.next()
+            }
+            Some(Back::Buffered(elems)) => elems.pop_front().map(Cow::Owned),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match &self.back {
+            None => self.front.size_hint(),
+            Some(Back::Walk(_, remaining)) => (*remaining, Some(*remaining)),
+            Some(Back::Buffered(elems)) => (elems.len(), Some(elems.len())),
+        }
+    }
+}
+
+impl<A: Clone> DoubleEndedIterator for Iter<'_, A> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let back = self
+            .back
+            .get_or_insert_with(|| match self.front.size_hint() {
+                (lower, Some(upper)) if lower == upper => {
+                    Back::Walk(Walk::new(self.root, true), lower)
+                }
+                _ => {
+                    let elems = core::iter::from_fn(|| self.front.next());
+                    Back::Buffered(elems.map(Cow::into_owned).collect())
+                }
+            });
+        match back {
+            Back::Walk(_, 0) => None,
+            Back::Walk(walk, remaining) => {
+                *remaining -= 1;
+                walk.next()
+            }
+            Back::Buffered(elems) => elems.pop_back().map(Cow::Owned),
+        }
+    }
+}
+
+impl<A: Clone> FusedIterator for Iter<'_, A> {}
+
+impl<'a, A: Clone> IntoIterator for &'a Chunk<A> {
+    type Item = Cow<'a, A>;
+    type IntoIter = Iter<'a, A>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// A consuming iterator over the elements of a [`Chunk`].
+///
+/// This struct is created by the [`into_iter`](IntoIterator::into_iter) method on [`Chunk`].
+/// Elements are moved out of nodes that are uniquely owned by the iterated chunk; only
+/// elements of nodes that are still shared with another chunk are cloned. Use
+/// [`Chunk::try_into_iter`] for elements that can't be cloned.
+///
+/// # Examples
+/// ```
+/// use tailcall_chunk::Chunk;
+///
+/// let chunk = Chunk::default().append(String::from("a")).append(String::from("b"));
+/// let upper: Vec<String> = chunk.into_iter().map(|s| s.to_uppercase()).collect();
+/// assert_eq!(upper, vec!["A", "B"]);
+/// ```
+pub struct IntoIter<A> {
+    stack: Vec<(OwnedFrame<A>, Cont<A>)>,
+}
+
+enum OwnedFrame<A> {
+    /// A uniquely owned node whose elements can be moved out, walked backwards if the flag
+    /// is set
+    Owned(Chunk<A>, bool),
+    /// A node that is shared with another chunk, walked backwards if the flag is set
+    Shared(Link<A>, bool),
+    /// The indexes of the remaining elements of a shared `Collect` node, yielded backwards
+    /// if the flag is set
+    SharedVec(Rc<Vec<A>>, Range<usize>, bool),
+    /// The remaining elements of a uniquely owned `Collect` node, yielded backwards if the
+    /// flag is set
+    Vec(vec::IntoIter<A>, bool),
+    /// The remaining elements of a `Map` node
+    Mapped(Box<dyn Iterator<Item = A>>),
+}
+
+impl<A> OwnedFrame<A> {
+    fn from_link(mut link: Link<A>, rev: bool) -> Self {
+        match link.take_unique() {
+            Some(chunk) => OwnedFrame::Owned(chunk, rev),
+            None => OwnedFrame::Shared(link, rev),
+        }
+    }
+
+    fn shared_vec(vec: Rc<Vec<A>>, rev: bool) -> Self {
+        let indexes = 0..vec.len();
+        OwnedFrame::SharedVec(vec, indexes, rev)
+    }
+
+    /// Returns the frame of a `Map` node, which evaluates its elements in walking order.
+    fn mapped(mapped: &Mapped<A>, rev: bool) -> Self {
+        OwnedFrame::Mapped(mapped.iter(rev))
+    }
+}
+
+impl<A> IntoIter<A> {
+    pub(crate) fn new(chunk: Chunk<A>) -> Self {
+        IntoIter {
+            stack: vec![(OwnedFrame::Owned(chunk, false), None)],
+        }
+    }
+
+    fn empty() -> Self {
+        IntoIter { stack: Vec::new() }
+    }
+
+    fn push(&mut self, chunk: Chunk<A>, cont: Cont<A>, rev: bool) {
+        self.stack.push((OwnedFrame::Owned(chunk, rev), cont));
+    }
+
+    /// Returns the next element, using `clone` to copy elements of shared nodes.
+    ///
+    /// Fails with [`SharedElementError`] if `clone` returns `None`.
+    fn try_next(
+        &mut self,
+        clone: &dyn Fn(&A) -> Option<A>,
+    ) -> Result<Option<A>, SharedElementError> {
+        let cloned = |a: &A| clone(a).ok_or(SharedElementError);
+        loop {
+            let Some((frame, cont)) = self.stack.last_mut() else {
+                return Ok(None);
+            };
+            if Step::is_exhausted(cont) {
+                self.stack.pop();
+                continue;
+            }
+            let elem = match frame {
+                OwnedFrame::Vec(iter, rev) => {
+                    let next = if *rev { iter.next_back() } else { iter.next() };
+                    match next {
+                        Some(a) => (a, cont.clone()),
+                        None => {
+                            self.stack.pop();
+                            continue;
+                        }
+                    }
+                }
+                OwnedFrame::Mapped(iter) => match iter.next() {
+                    Some(a) => (a, cont.clone()),
+                    None => {
+                        self.stack.pop();
+                        continue;
+                    }
+                },
+                OwnedFrame::SharedVec(vec, indexes, rev) => {
+                    let next = if *rev {
+                        indexes.next_back()
+                    } else {
+                        indexes.next()
+                    };
+                    match next {
+                        Some(index) => (cloned(&vec[index])?, cont.clone()),
+                        None => {
+                            self.stack.pop();
+                            continue;
+                        }
+                    }
+                }
+                OwnedFrame::Owned(..) | OwnedFrame::Shared(..) => {
+                    let Some((frame, cont)) = self.stack.pop() else {
+                        return Ok(None);
+                    };
+                    match frame {
+                        OwnedFrame::Owned(chunk, rev) => match chunk.node {
+                            Node::Empty => continue,
+                            Node::Single(a) => (a, cont),
+                            Node::Collect(vec) => {
+                                let frame = match Rc::try_unwrap(vec) {
+                                    Ok(vec) => OwnedFrame::Vec(vec.into_iter(), rev),
+                                    Err(vec) => OwnedFrame::shared_vec(vec, rev),
+                                };
+                                self.stack.push((frame, cont));
+                                continue;
+                            }
+                            Node::Slice(vec, range) => {
+                                let frame = match Rc::try_unwrap(vec) {
+                                    Ok(mut vec) => {
+                                        vec.truncate(range.end);
+                                        vec.drain(..range.start);
+                                        OwnedFrame::Vec(vec.into_iter(), rev)
+                                    }
+                                    Err(vec) => OwnedFrame::SharedVec(vec, range, rev),
+                                };
+                                self.stack.push((frame, cont));
+                                continue;
+                            }
+                            Node::Concat(a, b) => {
+                                let (first, second) = if rev { (b, a) } else { (a, b) };
+                                self.stack
+                                    .push((OwnedFrame::from_link(second, rev), cont.clone()));
+                                self.stack.push((OwnedFrame::from_link(first, rev), cont));
+                                continue;
+                            }
+                            Node::Transform(a, op) if rev && op.is_order_dependent() => {
+                                let node = Node::Transform(a, op).into();
+                                let vec = Self::collect(OwnedFrame::Owned(node, false), clone)?;
+                                self.stack
+                                    .push((OwnedFrame::Vec(vec.into_iter(), true), cont));
+                                continue;
+                            }
+                            Node::Transform(a, op) => {
+                                let cont = Step::push(op, cont, rev);
+                                self.stack.push((OwnedFrame::from_link(a, rev), cont));
+                                continue;
+                            }
+                            Node::Map(mapped) => {
+                                self.stack.push((OwnedFrame::mapped(&mapped, rev), cont));
+                                continue;
+                            }
+                            Node::Memo(a, mut cell) => {
+                                let frame = match Rc::get_mut(&mut cell).map(|cell| cell.take()) {
+                                    // Nothing else can read the cache, so skip filling it
+                                    Some(None) => OwnedFrame::from_link(a, rev),
+                                    Some(Some(vec)) => {
+                                        OwnedFrame::Owned(Node::Collect(vec).into(), rev)
+                                    }
+                                    None => OwnedFrame::shared_vec(
+                                        Self::memoized(&a, &*cell, clone)?,
+                                        rev,
+                                    ),
+                                };
+                                self.stack.push((frame, cont));
+                                continue;
+                            }
+                            Node::Reverse(a) => {
+                                self.stack.push((OwnedFrame::from_link(a, !rev), cont));
+                                continue;
+                            }
+                        },
+                        OwnedFrame::Shared(link, rev) => match &link.node {
+                            Node::Empty => continue,
+                            Node::Single(a) => (cloned(a)?, cont),
+                            Node::Collect(vec) => {
+                                self.stack
+                                    .push((OwnedFrame::shared_vec(vec.clone(), rev), cont));
+                                continue;
+                            }
+                            Node::Slice(vec, range) => {
+                                let frame = OwnedFrame::SharedVec(vec.clone(), range.clone(), rev);
+                                self.stack.push((frame, cont));
+                                continue;
+                            }
+                            Node::Concat(a, b) => {
+                                let (first, second) = if rev { (b, a) } else { (a, b) };
+                                self.stack
+                                    .push((OwnedFrame::Shared(second.clone(), rev), cont.clone()));
+                                self.stack
+                                    .push((OwnedFrame::Shared(first.clone(), rev), cont));
+                                continue;
+                            }
+                            Node::Transform(_, op) if rev && op.is_order_dependent() => {
+                                let frame = OwnedFrame::Shared(link.clone(), false);
+                                let vec = Self::collect(frame, clone)?;
+                                self.stack
+                                    .push((OwnedFrame::Vec(vec.into_iter(), true), cont));
+                                continue;
+                            }
+                            Node::Transform(a, op) => {
+                                let cont = Step::push(op.clone(), cont, rev);
+                                self.stack.push((OwnedFrame::Shared(a.clone(), rev), cont));
+                                continue;
+                            }
+                            Node::Map(mapped) => {
+                                self.stack.push((OwnedFrame::mapped(mapped, rev), cont));
+                                continue;
+                            }
+                            Node::Memo(a, cell) => {
+                                let vec = Self::memoized(a, &**cell, clone)?;
+                                self.stack.push((OwnedFrame::shared_vec(vec, rev), cont));
+                                continue;
+                            }
+                            Node::Reverse(a) => {
+                                self.stack.push((OwnedFrame::Shared(a.clone(), !rev), cont));
+                                continue;
+                            }
+                        },
+                        OwnedFrame::SharedVec(..) | OwnedFrame::Vec(..) | OwnedFrame::Mapped(_) => {
+                            continue
+                        }
+                    }
+                }
+            };
+
+            if let Some(a) = Step::run(elem.0, &elem.1, self) {
+                return Ok(Some(a));
+            }
+        }
+    }
+
+    /// Evaluates the elements of `frame`, using `clone` to copy elements of shared nodes.
+    fn collect(
+        frame: OwnedFrame<A>,
+        clone: &dyn Fn(&A) -> Option<A>,
+    ) -> Result<Vec<A>, SharedElementError> {
+        let mut iter = IntoIter {
+            stack: vec![(frame, None)],
+        };
+        let mut vec = Vec::with_capacity(iter.size_hint().0);
+        while let Some(a) = iter.try_next(clone)? {
+            vec.push(a);
+        }
+        Ok(vec)
+    }
+
+    /// Returns the cached elements of a `Memo` node, evaluating `source` if needed.
+    ///
+    /// Unlike [`Chunk::memoized`], this doesn't require `A: Clone`. Like it, the memoized
+    /// chunks nested in `source` are evaluated first, innermost first.
+    fn memoized(
+        source: &Link<A>,
+        cell: &dyn MemoCache<A>,
+        clone: &dyn Fn(&A) -> Option<A>,
+    ) -> Result<Rc<Vec<A>>, SharedElementError> {
+        if let Some(vec) = cell.get() {
+            return Ok(vec.clone());
+        }
+        for (source, cell) in source.pending_memos().into_iter().rev() {
+            if cell.get().is_none() {
+                let vec = Self::collect(OwnedFrame::Shared(source.clone(), false), clone)?;
+                cell.set(Rc::new(vec));
+            }
+        }
+        let vec = Self::collect(OwnedFrame::Shared(source.clone(), false), clone)?;
+        Ok(cell.set(Rc::new(vec)).clone())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let hints = self.stack.iter().map(|(frame, cont)| match frame {
+            _ if !Step::is_one_to_one(cont) => (0, None),
+            OwnedFrame::Owned(chunk, _) => chunk.size_hint(),
+            OwnedFrame::Shared(link, _) => link.size_hint(),
+            OwnedFrame::SharedVec(_, indexes, _) => (indexes.len(), Some(indexes.len())),
+            OwnedFrame::Vec(iter, _) => (iter.len(), Some(iter.len())),
+            OwnedFrame::Mapped(iter) => iter.size_hint(),
+        });
+        sum_hints(hints)
+    }
+
+    /// Collects the remaining elements, failing on the first element of a shared node.
+    pub(crate) fn try_collect(mut self) -> Result<Vec<A>, SharedElementError> {
+        let mut vec = Vec::with_capacity(self.size_hint().0);
+        while let Some(a) = self.try_next(&|_| None)? {
+            vec.push(a);
+        }
+        Ok(vec)
+    }
+}
+
+impl<A: Clone> Iterator for IntoIter<A> {
+    type Item = A;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.try_next(&|a| Some(a.clone())).ok().flatten()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        IntoIter::size_hint(self)
+    }
+}
+
+impl<A: Clone> FusedIterator for IntoIter<A> {}
+
+impl<A: Clone> IntoIterator for Chunk<A> {
+    type Item = A;
+    type IntoIter = IntoIter<A>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter::new(self)
+    }
+}
+
+/// A consuming iterator over the elements of a [`Chunk`] that doesn't require `A: Clone`.
+///
+/// This struct is created by [`Chunk::try_into_iter`]. Like [`IntoIter`], it moves elements
+/// out of the nodes that are uniquely owned by the iterated chunk. An element of a node that
+/// is still shared with another chunk can't be moved out, so it is yielded as
+/// `Err(SharedElementError)`, after which the iterator ends.
+///
+/// # Examples
+/// ```
+/// use tailcall_chunk::{Chunk, SharedElementError};
+///
+/// // `Box<dyn Error>` can't be cloned
+/// let errors: Chunk<Box<dyn std::error::Error>> = Chunk::default()
+///     .append("first".into())
+///     .append("second".into());
+/// for error in errors.try_into_iter() {
+///     assert!(!error.unwrap().to_string().is_empty());
+/// }
+///
+/// let shared: Chunk<_> = vec![std::cell::Cell::new(1)].into_iter().collect();
+/// let version = shared.clone();
+/// let mut iter = shared.try_into_iter();
+/// assert_eq!(iter.next(), Some(Err(SharedElementError)));
+/// assert_eq!(iter.next(), None);
+/// ```
+pub struct TryIntoIter<A> {
+    inner: IntoIter<A>,
+}
+
+impl<A> TryIntoIter<A> {
+    pub(crate) fn new(chunk: Chunk<A>) -> Self {
+        TryIntoIter {
+            inner: IntoIter::new(chunk),
+        }
+    }
+}
+
+impl<A> Iterator for TryIntoIter<A> {
+    type Item = Result<A, SharedElementError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.inner.try_next(&|_| None) {
+            Ok(a) => a.map(Ok),
+            Err(e) => {
+                // The remaining elements are dropped, so that the iterator ends here
+                self.inner = IntoIter::empty();
+                Some(Err(e))
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // An error takes the place of all the remaining elements
+        let (lower, upper) = self.inner.size_hint();
+        (lower.min(1), upper)
+    }
+}
+
+impl<A> FusedIterator for TryIntoIter<A> {}
+
+/// Adds up the size hints of consecutive parts of a sequence.
+fn sum_hints(hints: impl Iterator<Item = (usize, Option<usize>)>) -> (usize, Option<usize>) {
+    hints.fold((0, Some(0)), |(lower, upper), (l, u)| {
+        let upper = match (upper, u) {
+            (Some(upper), Some(u)) => upper.checked_add(u),
+            _ => None,
+        };
+        (lower.saturating_add(l), upper)
+    })
+}
+
+/// The error returned by [`Chunk::try_into_vec`] and [`Chunk::try_into_iter`] when an
+/// element cannot be moved out because its node is still shared with another chunk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SharedElementError;
+
+impl fmt::Display for SharedElementError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("cannot move an element out of a chunk node that is shared with another chunk")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SharedElementError {}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+
+    #[test]
+    fn test_iter_matches_as_vec() {
+        let chunks = vec![
+            Chunk::default(),
+            Chunk::new(1),
+            Chunk::default().append(1).append(2).append(3),
+            Chunk::default()
+                .prepend(1)
+                .prepend(2)
+                .concat((3..6).collect()),
+            (1..4).collect::<Chunk<_>>().transform(|x| x * 10),
+            (1..4).collect::<Chunk<_>>().map(|x| x * 10).prepend(0),
+            Chunk::default()
+                .append("ab")
+                .append("c")
+                .flat_map(|s| s.bytes().map(i32::from).collect()),
+            Chunk::default()
+                .append(1)
+                .append(2)
+                .transform_flatten(|x| Chunk::default().append(x).append(x + 1))
+                .transform_flatten(|x| {
+                    if x % 2 == 0 {
+                        Chunk::new(x)
+                    } else {
+                        Chunk::default()
+                    }
+                }),
+        ];
+
+        for chunk in chunks {
+            let vec: Vec<i32> = chunk.iter().map(Cow::into_owned).collect();
+            assert_eq!(vec, chunk.as_vec());
+        }
+    }
+
+    #[test]
+    fn test_iter_double_ended() {
+        let known: Chunk<_> = Chunk::new(0).concat((1..6).collect()).transform(|x| x * 10);
+        let filtered = known.clone().filter(|x| x % 20 == 0);
+
+        for chunk in [known, filtered] {
+            let mut expected = chunk.as_vec();
+            let backwards: Vec<_> = chunk.iter().rev().map(Cow::into_owned).collect();
+            expected.reverse();
+            assert_eq!(backwards, expected);
+            expected.reverse();
+
+            // Both ends meet in the middle without yielding an element twice
+            let mut iter = chunk.iter();
+            let mut front = Vec::new();
+            let mut back = Vec::new();
+            front.extend(iter.next().map(Cow::into_owned));
+            while let Some(a) = iter.next_back() {
+                back.push(a.into_owned());
+                front.extend(iter.next().map(Cow::into_owned));
+            }
+            assert_eq!(iter.size_hint(), (0, Some(0)));
+            back.reverse();
+            front.extend(back);
+            assert_eq!(front, expected);
+        }
+    }
+
+    #[test]
+    fn test_iter_back_is_lazy() {
+        let evaluated = Rc::new(Cell::new(0));
+        let counter = evaluated.clone();
+        let chunk: Chunk<_> = (0..100).collect();
+        let chunk = chunk.transform(move |x| {
+            counter.set(counter.get() + 1);
+            x
+        });
+        let last = chunk.iter().next_back().map(Cow::into_owned);
+        assert_eq!(last, Some(99));
+        assert_eq!(evaluated.get(), 1);
+    }
+
+    #[test]
+    fn test_iter_borrows_stored_elements() {
+        let chunk =
+            Chunk::new(String::from("a")).concat(vec![String::from("b")].into_iter().collect());
+        assert!(chunk.iter().all(|a| matches!(a, Cow::Borrowed(_))));
+
+        let transformed = chunk.transform(|s| s.to_uppercase());
+        let items: Vec<_> = transformed.iter().collect();
+        assert!(items.iter().all(|a| matches!(a, Cow::Owned(_))));
+        assert_eq!(
+            items,
+            vec![Cow::<String>::Owned("A".into()), Cow::Owned("B".into())]
+        );
+    }
+
+    #[test]
+    fn test_iter_is_lazy() {
+        let calls = Rc::new(Cell::new(0));
+        let counter = calls.clone();
+        let chunk = (0..100).collect::<Chunk<_>>().transform(move |x| {
+            counter.set(counter.get() + 1);
+            x * 2
+        });
+
+        let firsts: Vec<_> = chunk.iter().take(3).map(Cow::into_owned).collect();
+        assert_eq!(firsts, vec![0, 2, 4]);
+        assert_eq!(calls.get(), 3);
+    }
+
+    #[test]
+    fn test_into_iterator_for_ref() {
+        let chunk = Chunk::default().append(1).append(2).append(3);
+        let mut sum = 0;
+        for a in &chunk {
+            sum += *a;
+        }
+        assert_eq!(sum, 6);
+    }
+
+    #[test]
+    fn test_iter_deep_chunk() {
+        let mut chunk = Chunk::default();
+        for i in (0..100_000).rev() {
+            chunk = chunk.prepend(i);
+        }
+        assert!(chunk.iter().enumerate().all(|(i, a)| *a == i));
+    }
+
+    #[test]
+    fn test_into_iter_moves_unique_elements() {
+        let chunk = Chunk::default()
+            .append(String::from("a"))
+            .prepend(String::from("b"))
+            .concat(vec![String::from("c")].into_iter().collect())
+            .transform(|s| s + "!");
+        let vec: Vec<_> = chunk.clone().into_iter().collect();
+        assert_eq!(vec, vec!["b!", "a!", "c!"]);
+        assert_eq!(chunk.into_vec(), vec!["b!", "a!", "c!"]);
+    }
+
+    #[test]
+    fn test_into_iter_clones_shared_elements() {
+        let base = Chunk::default().append(1).append(2);
+        let version = base.clone().append(3).prepend(0);
+        assert_eq!(version.into_vec(), vec![0, 1, 2, 3]);
+        assert_eq!(base.into_iter().collect::<Vec<_>>(), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_try_into_vec() {
+        // A type that can't be cloned
+        #[derive(Debug, PartialEq)]
+        struct Handle(i32);
+
+        let chunk = Chunk::default()
+            .append(Handle(1))
+            .append(Handle(2))
+            .prepend(Handle(0))
+            .transform_flatten(|h| Chunk::default().append(Handle(h.0)).append(Handle(-h.0)));
+        assert_eq!(
+            chunk.try_into_vec(),
+            Ok(vec![
+                Handle(0),
+                Handle(0),
+                Handle(1),
+                Handle(-1),
+                Handle(2),
+                Handle(-2)
+            ])
+        );
+
+        let shared = Rc::new(Handle(7));
+        let chunk = Chunk::new(shared.clone()).concat(Chunk::new(shared));
+        assert_eq!(chunk.try_into_vec().map(|v| v.len()), Ok(2));
+    }
+
+    #[test]
+    fn test_try_into_iter() {
+        // A type that can't be cloned
+        #[derive(Debug, PartialEq)]
+        struct Handle(i32);
+
+        let chunk = Chunk::default()
+            .append(Handle(1))
+            .append(Handle(2))
+            .prepend(Handle(0))
+            .filter(|h| h.0 != 1);
+        let mut seen = Vec::new();
+        for handle in chunk.try_into_iter() {
+            seen.push(handle.unwrap().0);
+        }
+        assert_eq!(seen, vec![0, 2]);
+
+        // Stops at the first element of a shared node
+        let shared: Chunk<_> = vec![Cell::new(1), Cell::new(2)].into_iter().collect();
+        let chunk = Chunk::new(Cell::new(0)).concat(shared.clone());
+        let items: Vec<_> = chunk
+            .try_into_iter()
+            .map(|r| r.map(Cell::into_inner))
+            .collect();
+        assert_eq!(items, vec![Ok(0), Err(SharedElementError)]);
+    }
+
+    #[test]
+    fn test_try_into_vec_reports_shared_nodes() {
+        let chunk: Chunk<_> = vec![Cell::new(1), Cell::new(2)].into_iter().collect();
+        let _version = chunk.clone();
+        assert_eq!(chunk.try_into_vec(), Err(SharedElementError));
+    }
+
+    #[test]
+    fn test_iter_size_hint() {
+        let chunk = Chunk::default().append(1).append(2).prepend(0);
+        let mut iter = chunk.iter();
+        assert_eq!(iter.size_hint(), (3, Some(3)));
+        iter.next();
+        assert_eq!(iter.size_hint(), (2, Some(2)));
+
+        let mixed = chunk.clone().concat(chunk.transform_flatten(Chunk::new));
+        let mut iter = mixed.into_iter();
+        assert_eq!(iter.size_hint(), (3, None));
+        assert_eq!(iter.by_ref().count(), 6);
+        assert_eq!(iter.size_hint(), (0, Some(0)));
+    }
+}
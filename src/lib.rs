@@ -26,6 +26,8 @@
 //! - We use `Append` nodes for constant-time additions
 //! - The `Concat` variant enables efficient concatenation
 //! - `Rc` (Reference Counting) provides persistence and structural sharing
+//! - `uncons` and `unsnoc` rotate `Concat` nodes along the edge they descend, so that
+//!   elements can be taken off either end in amortized constant time
 //!
 //! # Example Usage
 //! ```rust
@@ -57,9 +59,18 @@
 //! | `new()`               | O(1)       | O(1)         | O(1)         |
 //! | `append()`            | O(1)       | O(1)         | O(1)         |
 //! | `concat()`            | O(1)       | O(1)         | O(1)         |
+//! | `concat_balanced()`   | O(n)       | O(1)         | O(1)         |
 //! | `transform()`         | O(1)       | O(1)         | O(1)         |
 //! | `transform_flatten()` | O(1)       | O(1)         | O(1)         |
+//! | `map()`               | O(1)       | O(1)         | O(1)         |
+//! | `flat_map()`          | O(1)       | O(1)         | O(1)         |
+//! | `filter()`            | O(1)       | O(1)         | O(1)         |
+//! | `reverse()`           | O(1)       | O(1)         | O(1)         |
+//! | `uncons()`            | O(n)       | O(1)         | O(1)         |
+//! | `unsnoc()`            | O(n)       | O(1)         | O(1)         |
+//! | `memoize()`           | O(1)       | O(1)         | O(1)         |
 //! | `as_vec()`            | O(n)       | O(n)         | O(n)         |
+//! | `len()`               | O(1)       | O(1)         | O(1)         |
 //! | `clone()`             | O(1)       | O(1)         | O(1)         |
 //!
 //! ## Amortized Analysis Details
@@ -113,11 +124,17 @@
 //!
 //! # Implementation Details
 //!
-//! The `Chunk<A>` type is implemented as an enum with four variants:
+//! A `Chunk<A>` is a tree of nodes, which are private so that no version can be modified
+//! through another one that shares its nodes. The main kinds of nodes are:
 //! - `Empty`: Represents an empty chunk
-//! - `Append`: Represents a single element appended to another chunk
+//! - `Single`: Represents a chunk with a single element
 //! - `Concat`: Represents the concatenation of two chunks
-//! - `TransformFlatten`: Represents a lazy transformation and flattening of elements
+//! - `Collect`: Represents elements stored contiguously
+//! - `Transform`: Represents a lazy operation on every element, such as a transformation,
+//!   a flattening or a filter
+//! - `Reverse`: Represents the elements of a chunk in reverse order
+//!
+//! They can be inspected, read-only, through [`Chunk::view`].
 //!
 //! The data structure achieves its performance characteristics through:
 //! - Structural sharing using `Rc`
@@ -139,11 +156,61 @@
 //! let version2 = original.clone().append(4);  // Both versions share data
 //! ```
 //!
+//! # Thread Safety
+//!
+//! `Chunk` shares its nodes through `Rc` and is therefore neither `Send` nor `Sync`. When a chunk
+//! needs to cross thread boundaries, use [`SyncChunk`] instead: it exposes the same API but is
+//! backed by `Arc` and only accepts `Send + Sync` transformation closures.
+//!
+//! ```rust
+//! use tailcall_chunk::SyncChunk;
+//!
+//! let chunk = SyncChunk::default().append(1).append(2);
+//! let doubled = std::thread::spawn(move || chunk.transform(|x| x * 2).as_vec());
+//! assert_eq!(doubled.join().unwrap(), vec![2, 4]);
+//! ```
+//!
+//! # Cargo Features
+//!
+//! - `std` (default): implements `std::error::Error` for `SharedElementError`, and `Sink` for
+//!   the senders of `std::sync::mpsc` channels. Without it the crate is `no_std` and only
+//!   depends on `core` and `alloc`.
+//! - `rayon`: adds `SyncChunk::par_iter` and `SyncChunk::par_as_vec`, which evaluate a chunk
+//!   on all cores.
+//! - `serde`: implements `Serialize` and `Deserialize` for `Chunk`, which is represented as a
+//!   sequence of its elements.
+//!
 //! # References
 //!
 //! 1. Ralf Hinze and Ross Paterson. "Finger Trees: A Simple General-purpose Data Structure",
 //!    Journal of Functional Programming 16(2):197-217, 2006.
 //! 2. Chris Okasaki. "Purely Functional Data Structures", Cambridge University Press, 1998.
 
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+mod cause;
 mod chunk;
+mod cmp;
+mod convert;
+mod iter;
+#[cfg(feature = "rayon")]
+mod par;
+#[cfg(feature = "serde")]
+mod serde;
+mod sink;
+mod sync;
+mod valid;
+pub use cause::*;
 pub use chunk::*;
+pub use iter::*;
+pub use sink::*;
+pub use sync::*;
+pub use valid::*;
+
+/// Re-exports used by the [`chunk!`] macro, so that it also works in `no_std` crates.
+#[doc(hidden)]
+pub mod __private {
+    pub use alloc::{vec, vec::Vec};
+}
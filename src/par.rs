@@ -0,0 +1,243 @@
+//! Parallel evaluation of a [`SyncChunk`], behind the `rayon` feature.
+//!
+//! The chunk is split along its `Concat` nodes and `Collect` vectors, and the pieces are
+//! evaluated on the [rayon] thread pool. The elements fed through a `TransformFlatten`
+//! node are spread over the pool as well, so expensive closures run in parallel. The
+//! output always keeps the order of the elements.
+//!
+//! # Example
+//! ```
+//! use rayon::prelude::*;
+//! use tailcall_chunk::SyncChunk;
+//!
+//! let chunk: SyncChunk<u64> = (0..1000).collect();
+//! let squares = chunk.transform(|x| x * x);
+//!
+//! assert_eq!(squares.par_iter().sum::<u64>(), (0..1000).map(|x| x * x).sum());
+//! assert_eq!(squares.par_as_vec(), squares.as_vec());
+//! ```
+
+use rayon::{iter::split, prelude::*};
+
+use crate::{sync::SyncNode, SyncChunk};
+
+impl<A: Clone + Send + Sync> SyncChunk<A> {
+    /// Returns a parallel iterator over the elements of the chunk.
+    ///
+    /// The iterator splits the chunk along its `Concat` nodes and `Collect` vectors, so
+    /// independent parts of the chunk are evaluated on different threads. Collecting it into
+    /// a `Vec` keeps the elements in order.
+    ///
+    /// # Examples
+    /// ```
+    /// use rayon::prelude::*;
+    /// use tailcall_chunk::SyncChunk;
+    ///
+    /// let chunk = SyncChunk::default().append(1).append(2).append(3);
+    /// let doubled: Vec<i32> = chunk.par_iter().map(|x| x * 2).collect();
+    /// assert_eq!(doubled, vec![2, 4, 6]);
+    /// ```
+    pub fn par_iter(&self) -> impl ParallelIterator<Item = A> + '_ {
+        self.par_iter_at(0)
+    }
+
+    /// Converts the chunk into a vector of its elements, evaluating it in parallel.
+    ///
+    /// This produces the same vector as [`as_vec`](SyncChunk::as_vec). It pays off for large
+    /// chunks, or chunks with expensive [`transform`](SyncChunk::transform) closures.
+    ///
+    /// # Examples
+    /// ```
+    /// use tailcall_chunk::SyncChunk;
+    ///
+    /// let chunk: SyncChunk<_> = (0..100).collect();
+    /// let expanded = chunk.transform_flatten(|x| SyncChunk::default().append(x).append(x));
+    /// assert_eq!(expanded.par_as_vec(), expanded.as_vec());
+    /// ```
+    pub fn par_as_vec(&self) -> Vec<A> {
+        self.par_iter().collect()
+    }
+
+    /// Returns a parallel iterator over the elements of a chunk found below `depth` nested
+    /// parallel evaluations.
+    fn par_iter_at(&self, depth: usize) -> impl ParallelIterator<Item = A> + '_ {
+        split(Piece::Nodes(vec![self], depth), Piece::split).flat_map_iter(Piece::into_vec)
+    }
+}
+
+/// How many parallel evaluations can be nested in one another. Each of them takes a few
+/// frames on the stack of a pool thread, so chunks that nest `TransformFlatten` nodes any
+/// deeper are evaluated sequentially from there on.
+const MAX_NESTING: usize = 64;
+
+/// A consecutive part of a chunk that is evaluated as one unit of parallel work.
+enum Piece<'a, A> {
+    /// A sequence of adjacent nodes, below the given number of nested parallel evaluations
+    Nodes(Vec<&'a SyncChunk<A>>, usize),
+    /// A part of the vector of a `Collect` node
+    Slice(&'a [A]),
+}
+
+impl<'a, A: Clone + Send + Sync> Piece<'a, A> {
+    /// Splits the piece in two halves, if it can be split without evaluating anything.
+    fn split(self) -> (Self, Option<Self>) {
+        match self {
+            Piece::Nodes(mut nodes, depth) if nodes.len() > 1 => {
+                let right = nodes.split_off(nodes.len() / 2);
+                (Piece::Nodes(nodes, depth), Some(Piece::Nodes(right, depth)))
+            }
+            Piece::Nodes(nodes, depth) => match nodes.first().map(|chunk| &chunk.node) {
+                Some(SyncNode::Concat(a, b)) => (
+                    Piece::Nodes(vec![a], depth),
+                    Some(Piece::Nodes(vec![b], depth)),
+                ),
+                Some(SyncNode::Collect(vec)) if vec.len() > 1 => {
+                    let (left, right) = vec.split_at(vec.len() / 2);
+                    (Piece::Slice(left), Some(Piece::Slice(right)))
+                }
+                _ => (Piece::Nodes(nodes, depth), None),
+            },
+            Piece::Slice(slice) if slice.len() > 1 => {
+                let (left, right) = slice.split_at(slice.len() / 2);
+                (Piece::Slice(left), Some(Piece::Slice(right)))
+            }
+            piece => (piece, None),
+        }
+    }
+
+    /// Evaluates the piece on the current thread, except for the `TransformFlatten` nodes it
+    /// contains, whose elements are still transformed in parallel.
+    ///
+    /// A chain of `TransformFlatten` nodes is applied one level at a time, innermost first,
+    /// rather than through a nested parallel evaluation per node.
+    fn into_vec(self) -> Vec<A> {
+        let (mut stack, depth) = match self {
+            Piece::Slice(slice) => return slice.to_vec(),
+            Piece::Nodes(mut nodes, depth) => {
+                nodes.reverse();
+                (nodes, depth)
+            }
+        };
+
+        let mut buf = Vec::new();
+        while let Some(node) = stack.pop() {
+            match &node.node {
+                SyncNode::Empty => {}
+                SyncNode::Single(a) => buf.push(a.clone()),
+                SyncNode::Collect(vec) => buf.extend(vec.iter().cloned()),
+                SyncNode::Concat(a, b) => {
+                    stack.push(b);
+                    stack.push(a);
+                }
+                SyncNode::TransformFlatten(..) if depth < MAX_NESTING => {
+                    let mut functions = Vec::new();
+                    let mut source = node;
+                    while let SyncNode::TransformFlatten(a, f) = &source.node {
+                        functions.push(f);
+                        source = a;
+                    }
+                    let mut elems: Vec<_> = source.par_iter_at(depth + 1).collect();
+                    for f in functions.into_iter().rev() {
+                        elems = elems
+                            .into_par_iter()
+                            .flat_map(|a| f(a).par_iter_at(depth + 1).collect::<Vec<_>>())
+                            .collect();
+                    }
+                    buf.append(&mut elems);
+                }
+                SyncNode::TransformFlatten(..) => node.as_vec_mut(&mut buf),
+            }
+        }
+        buf
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashSet,
+        sync::{Arc, Mutex},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_par_as_vec_matches_as_vec() {
+        let chunks: Vec<SyncChunk<i32>> = vec![
+            SyncChunk::default(),
+            SyncChunk::new(1),
+            (0..1000).collect(),
+            (0..100).fold(SyncChunk::default(), |chunk, i| chunk.prepend(i)),
+            (0..100).fold(SyncChunk::default(), |chunk, i| {
+                chunk.concat((i * 10..i * 10 + 10).collect())
+            }),
+            (0..100)
+                .collect::<SyncChunk<_>>()
+                .transform(|x| x * 2)
+                .concat(SyncChunk::new(-1))
+                .transform_flatten(|x| SyncChunk::default().append(x).append(x + 1))
+                .transform_flatten(|x| {
+                    if x % 3 == 0 {
+                        SyncChunk::default()
+                    } else {
+                        (0..x).collect()
+                    }
+                }),
+        ];
+
+        for chunk in chunks {
+            assert_eq!(chunk.par_as_vec(), chunk.as_vec());
+            assert_eq!(chunk.par_iter().count(), chunk.as_vec().len());
+        }
+    }
+
+    #[test]
+    fn test_par_as_vec_keeps_order() {
+        let chunk = (0..10_000).fold(SyncChunk::default(), |chunk, i| chunk.append(i));
+        let chunk = chunk.transform(|x| x + 1);
+        assert_eq!(chunk.par_as_vec(), (1..=10_000).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_par_as_vec_deep_nesting() {
+        const DEPTH: usize = 100_000;
+
+        let mut transformed = SyncChunk::new(0);
+        for _ in 0..DEPTH {
+            transformed = transformed.transform(|x| x + 1);
+        }
+        assert_eq!(transformed.par_as_vec(), vec![DEPTH]);
+
+        // Transforms of concatenations, which can't be applied as a single chain
+        let mut nested = SyncChunk::new(0);
+        for _ in 0..DEPTH {
+            let empty = SyncChunk::default().transform(|x: usize| x);
+            nested = nested.transform(|x| x + 1).concat(empty);
+        }
+        assert_eq!(nested.par_as_vec(), vec![DEPTH]);
+        assert_eq!(nested.par_iter().count(), 1);
+    }
+
+    #[test]
+    fn test_par_as_vec_long_chain() {
+        let mut chunk = SyncChunk::default();
+        for i in (0..10_000).rev() {
+            chunk = chunk.prepend(i);
+        }
+        assert_eq!(chunk.par_as_vec(), (0..10_000).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_par_transform_runs_on_the_pool() {
+        let threads = Arc::new(Mutex::new(HashSet::new()));
+        let seen = threads.clone();
+        let chunk = (0..1000).collect::<SyncChunk<_>>().transform(move |x| {
+            seen.lock().unwrap().insert(rayon::current_thread_index());
+            x
+        });
+        chunk.par_as_vec();
+
+        // Every closure call happened on a worker thread of the pool
+        assert!(threads.lock().unwrap().iter().all(Option::is_some));
+    }
+}
@@ -0,0 +1,117 @@
+//! [`Serialize`] and [`Deserialize`] implementations for [`Chunk`], behind the `serde` feature.
+//!
+//! A chunk is represented as a plain sequence of its elements, so it is interchangeable
+//! with a `Vec<A>` in any serialized format.
+//!
+//! # Example
+//! ```
+//! use tailcall_chunk::Chunk;
+//!
+//! let chunk = Chunk::default().append(1).append(2).transform(|x| x * 10);
+//! let json = serde_json::to_string(&chunk).unwrap();
+//! assert_eq!(json, "[10,20]");
+//!
+//! let chunk: Chunk<i32> = serde_json::from_str(&json).unwrap();
+//! assert_eq!(chunk.as_vec(), vec![10, 20]);
+//! ```
+
+use alloc::vec::Vec;
+
+use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::Chunk;
+
+impl<A: Serialize + Clone> Serialize for Chunk<A> {
+    /// Serializes the chunk as a sequence of its elements.
+    ///
+    /// The elements are streamed to the serializer through [`Chunk::iter`], so pending
+    /// transformations are evaluated on the fly and no intermediate `Vec` is built.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+impl<'de, A: Deserialize<'de>> Deserialize<'de> for Chunk<A> {
+    /// Deserializes a sequence of elements into a single `Collect` node.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::deserialize(deserializer).map(Chunk::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ChunkNode;
+
+    fn round_trip<A>(chunk: &Chunk<A>) -> Chunk<A>
+    where
+        A: Serialize + for<'de> Deserialize<'de> + Clone,
+    {
+        let json = serde_json::to_string(chunk).unwrap();
+        serde_json::from_str(&json).unwrap()
+    }
+
+    #[test]
+    fn test_round_trip_every_variant() {
+        let chunks: Vec<Chunk<i32>> = vec![
+            Chunk::default(),
+            Chunk::new(1),
+            Chunk::from(vec![1, 2, 3]),
+            Chunk::default().prepend(1).prepend(0).append(2),
+            Chunk::default()
+                .append(1)
+                .append(2)
+                .transform_flatten(|x| Chunk::default().append(x).append(-x)),
+            Chunk::default()
+                .append("a")
+                .append("bc")
+                .map(|s| s.len() as i32),
+            Chunk::default().append(1).transform_memo(|x| x + 1),
+            Chunk::new(0).concat((1..=3).collect()).memoize(),
+            (0..10).collect::<Chunk<_>>().filter(|x| x % 3 == 0),
+            (0..10)
+                .collect::<Chunk<_>>()
+                .filter_map(|x| (x % 2 == 0).then_some(x / 2)),
+            (0..10).collect::<Chunk<_>>().take_while(|x| *x < 4),
+            (0..10).collect::<Chunk<_>>().skip_while(|x| *x < 4),
+            Chunk::from(vec![1, 2, 3]).append(4).reverse(),
+            Chunk::from(vec![0, 1, 2, 3]).uncons().unwrap().1,
+        ];
+
+        for chunk in chunks {
+            let decoded = round_trip(&chunk);
+            assert_eq!(decoded.as_vec(), chunk.as_vec());
+            assert!(matches!(decoded.view(), ChunkNode::Collect(_)));
+        }
+    }
+
+    #[test]
+    fn test_serialize_as_sequence() {
+        let chunk = Chunk::default()
+            .append("a".to_string())
+            .append("b".to_string())
+            .transform(|s| s.to_uppercase());
+        assert_eq!(serde_json::to_string(&chunk).unwrap(), r#"["A","B"]"#);
+
+        let empty: Chunk<String> = Chunk::default();
+        assert_eq!(serde_json::to_string(&empty).unwrap(), "[]");
+    }
+
+    #[test]
+    fn test_nested_chunks() {
+        #[derive(Clone, Serialize, Deserialize)]
+        struct Event {
+            name: String,
+            tags: Chunk<String>,
+        }
+
+        let events = Chunk::new(Event {
+            name: "start".into(),
+            tags: Chunk::new("a".to_string()).append("b".to_string()),
+        });
+        let decoded = round_trip(&events);
+        let event = decoded.first().unwrap();
+        assert_eq!(event.name, "start");
+        assert_eq!(event.tags.as_vec(), vec!["a", "b"]);
+    }
+}
@@ -0,0 +1,221 @@
+//! Destinations for the elements of a [`Chunk`](crate::Chunk).
+//!
+//! [`Chunk::send_to`](crate::Chunk::send_to) streams the elements of a chunk into a
+//! [`Sink`], evaluating pending transformations on the fly, so that writing them into a
+//! `VecDeque`, a `HashSet` or a channel doesn't need a staging `Vec`. Untransformed
+//! `Collect` nodes are handed over a whole slice at a time.
+
+use alloc::{collections::VecDeque, vec::Vec};
+use core::ops::ControlFlow;
+
+/// A destination that accepts elements one at a time, and may stop accepting them early.
+///
+/// Any [`Extend`] collection can be written to through
+/// [`Chunk::extend_into`](crate::Chunk::extend_into), and any closure through
+/// [`Chunk::for_each`](crate::Chunk::for_each), so this trait is only needed for
+/// destinations that can refuse elements.
+///
+/// # Examples
+/// ```
+/// use std::ops::ControlFlow;
+/// use tailcall_chunk::{Chunk, Sink};
+///
+/// // Keeps elements until the budget runs out
+/// struct Budget {
+///     left: i32,
+///     taken: Vec<i32>,
+/// }
+///
+/// impl Sink<i32> for Budget {
+///     fn accept(&mut self, a: i32) -> ControlFlow<()> {
+///         if a > self.left {
+///             return ControlFlow::Break(());
+///         }
+///         self.left -= a;
+///         self.taken.push(a);
+///         ControlFlow::Continue(())
+///     }
+/// }
+///
+/// let chunk: Chunk<_> = (1..=4).collect();
+/// let mut budget = Budget { left: 5, taken: Vec::new() };
+/// assert_eq!(chunk.send_to(&mut budget), ControlFlow::Break(()));
+/// assert_eq!(budget.taken, vec![1, 2]);
+/// ```
+pub trait Sink<A> {
+    /// Accepts an element, or returns `Break` if no more elements should be sent.
+    fn accept(&mut self, a: A) -> ControlFlow<()>;
+
+    /// Accepts the elements of `elems` in order, stopping at the first `Break`.
+    ///
+    /// Chunks send contiguous runs of elements through this method, so destinations that
+    /// can take them in bulk should override it.
+    fn accept_all(&mut self, elems: impl Iterator<Item = A>) -> ControlFlow<()> {
+        for a in elems {
+            self.accept(a)?;
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+impl<A> Sink<A> for Vec<A> {
+    fn accept(&mut self, a: A) -> ControlFlow<()> {
+        self.push(a);
+        ControlFlow::Continue(())
+    }
+
+    fn accept_all(&mut self, elems: impl Iterator<Item = A>) -> ControlFlow<()> {
+        self.extend(elems);
+        ControlFlow::Continue(())
+    }
+}
+
+impl<A> Sink<A> for VecDeque<A> {
+    fn accept(&mut self, a: A) -> ControlFlow<()> {
+        self.push_back(a);
+        ControlFlow::Continue(())
+    }
+
+    fn accept_all(&mut self, elems: impl Iterator<Item = A>) -> ControlFlow<()> {
+        self.extend(elems);
+        ControlFlow::Continue(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<A> Sink<A> for std::sync::mpsc::Sender<A> {
+    /// Sends the element down the channel, stopping once the receiver is dropped.
+    fn accept(&mut self, a: A) -> ControlFlow<()> {
+        match self.send(a) {
+            Ok(()) => ControlFlow::Continue(()),
+            Err(_) => ControlFlow::Break(()),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<A> Sink<A> for std::sync::mpsc::SyncSender<A> {
+    /// Sends the element down the channel, blocking while it is full and stopping once
+    /// the receiver is dropped.
+    fn accept(&mut self, a: A) -> ControlFlow<()> {
+        match self.send(a) {
+            Ok(()) => ControlFlow::Continue(()),
+            Err(_) => ControlFlow::Break(()),
+        }
+    }
+}
+
+/// Writes into any [`Extend`] collection, see [`Chunk::extend_into`](crate::Chunk::extend_into).
+pub(crate) struct Extender<'a, E>(pub(crate) &'a mut E);
+
+impl<A, E: Extend<A>> Sink<A> for Extender<'_, E> {
+    fn accept(&mut self, a: A) -> ControlFlow<()> {
+        self.0.extend(Some(a));
+        ControlFlow::Continue(())
+    }
+
+    fn accept_all(&mut self, elems: impl Iterator<Item = A>) -> ControlFlow<()> {
+        self.0.extend(elems);
+        ControlFlow::Continue(())
+    }
+}
+
+/// Calls a closure on every element until it breaks, keeping the value it broke with.
+pub(crate) struct TryForEach<F, B> {
+    pub(crate) f: F,
+    pub(crate) broken: Option<B>,
+}
+
+impl<A, B, F: FnMut(A) -> ControlFlow<B>> Sink<A> for TryForEach<F, B> {
+    fn accept(&mut self, a: A) -> ControlFlow<()> {
+        match (self.f)(a) {
+            ControlFlow::Continue(()) => ControlFlow::Continue(()),
+            ControlFlow::Break(b) => {
+                self.broken = Some(b);
+                ControlFlow::Break(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeSet, VecDeque};
+    #[cfg(feature = "std")]
+    use std::sync::mpsc;
+
+    use super::*;
+    use crate::Chunk;
+
+    fn chunk() -> Chunk<i32> {
+        Chunk::default()
+            .append(3)
+            .concat((1..=3).collect())
+            .transform_flatten(|x| Chunk::new(x).append(x * 10))
+    }
+
+    #[test]
+    fn test_collections() {
+        let mut deque = VecDeque::from([0]);
+        assert_eq!(chunk().send_to(&mut deque), ControlFlow::Continue(()));
+        assert_eq!(deque, [0, 3, 30, 1, 10, 2, 20, 3, 30]);
+
+        let mut set = BTreeSet::new();
+        chunk().extend_into(&mut set);
+        assert_eq!(
+            set.into_iter().collect::<Vec<_>>(),
+            vec![1, 2, 3, 10, 20, 30]
+        );
+
+        let mut text = String::from(">");
+        Chunk::from(vec!['a', 'b'])
+            .transform(|c| c.to_ascii_uppercase())
+            .extend_into(&mut text);
+        assert_eq!(text, ">AB");
+    }
+
+    #[test]
+    fn test_try_for_each_stops_early() {
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        let evaluated = Rc::new(Cell::new(0));
+        let counter = evaluated.clone();
+        let chunk = chunk().inspect(move |_| counter.set(counter.get() + 1));
+
+        let mut seen = Vec::new();
+        let found = chunk.try_for_each(|x| {
+            seen.push(x);
+            if x >= 10 {
+                ControlFlow::Break(x)
+            } else {
+                ControlFlow::Continue(())
+            }
+        });
+        assert_eq!(found, ControlFlow::Break(30));
+        assert_eq!(seen, vec![3, 30]);
+        assert_eq!(evaluated.get(), 2);
+
+        let mut sum = 0;
+        chunk.for_each(|x| sum += x);
+        assert_eq!(sum, 99);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_channels() {
+        let (sender, receiver) = mpsc::channel();
+        let producer = std::thread::spawn(move || {
+            let mut sender = sender;
+            chunk().send_to(&mut sender)
+        });
+        let received: Vec<_> = receiver.iter().collect();
+        assert_eq!(producer.join().unwrap(), ControlFlow::Continue(()));
+        assert_eq!(received, chunk().as_vec());
+
+        // Sending stops once the receiver is gone
+        let (mut sender, receiver) = mpsc::sync_channel(1);
+        drop(receiver);
+        assert_eq!(chunk().send_to(&mut sender), ControlFlow::Break(()));
+    }
+}
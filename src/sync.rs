@@ -0,0 +1,606 @@
+//! A thread-safe variant of [`Chunk`](crate::Chunk).
+//!
+//! [`SyncChunk`] offers the same persistent, lazily evaluated API as [`Chunk`](crate::Chunk),
+//! but shares its nodes through [`Arc`] instead of [`Rc`](std::rc::Rc) and only accepts
+//! `Send + Sync` closures. As a result a `SyncChunk<A>` is `Send` and `Sync` whenever `A` is,
+//! so it can be moved into other threads or async tasks without converting to a `Vec` first.
+//!
+//! # Example
+//! ```
+//! use tailcall_chunk::SyncChunk;
+//!
+//! let chunk = SyncChunk::default().append(1).append(2);
+//! let handle = std::thread::spawn(move || chunk.transform(|x| x * 10).as_vec());
+//!
+//! assert_eq!(handle.join().unwrap(), vec![10, 20]);
+//! ```
+
+use alloc::{sync::Arc, vec, vec::Vec};
+use core::ops::Deref;
+
+/// A thread-safe persistent data structure that provides efficient append and concatenation operations.
+///
+/// # Overview
+/// `SyncChunk<A>` mirrors [`Chunk<A>`](crate::Chunk) node for node. Structural sharing is done
+/// through [`Arc`], and the in-place push optimization of the `Collect` variant relies on
+/// [`Arc::get_mut`], which atomically checks that the vector is not shared with any other version.
+///
+/// # Performance
+/// - Append operation: O(1)
+/// - Concatenation operation: O(1)
+/// - Converting to Vec: O(n)
+///
+/// Atomic reference counting makes cloning and dropping slightly more expensive than with
+/// [`Chunk`](crate::Chunk), so prefer `Chunk` when values never leave a single thread.
+///
+/// # Implementation Details
+/// Like the nodes of a `Chunk`, the nodes of a `SyncChunk` are kept private, and can be
+/// inspected read-only through [`view`](SyncChunk::view).
+///
+/// # Examples
+/// ```
+/// use tailcall_chunk::SyncChunk;
+///
+/// let chunk1 = SyncChunk::default().append(1).append(2);
+/// let chunk2 = SyncChunk::default().append(3).append(4);
+///
+/// assert_eq!(chunk1.concat(chunk2).as_vec(), vec![1, 2, 3, 4]);
+/// ```
+#[derive(Clone)]
+pub struct SyncChunk<A> {
+    pub(crate) node: SyncNode<A>,
+}
+
+/// The nodes a [`SyncChunk`] is built from.
+#[derive(Clone)]
+pub(crate) enum SyncNode<A> {
+    /// Represents an empty chunk with no elements
+    Empty,
+    /// Represents a chunk containing exactly one element
+    Single(A),
+    /// Represents the concatenation of two chunks, enabling O(1) concatenation
+    Concat(SyncLink<A>, SyncLink<A>),
+    /// Represents a collection of elements
+    Collect(Arc<Vec<A>>),
+    /// Represents a lazy transformation that flattens elements
+    TransformFlatten(SyncLink<A>, FlattenFn<A>),
+}
+
+impl<A> From<SyncNode<A>> for SyncChunk<A> {
+    fn from(node: SyncNode<A>) -> Self {
+        SyncChunk { node }
+    }
+}
+
+/// A read-only view of the root node of a [`SyncChunk`], returned by [`SyncChunk::view`].
+///
+/// Like [`ChunkNode`](crate::ChunkNode), it gives no access to the shared nodes themselves.
+/// New kinds of nodes may be added in the future.
+#[non_exhaustive]
+pub enum SyncChunkNode<'a, A> {
+    /// A chunk with no elements
+    Empty,
+    /// A chunk containing exactly one element
+    Single(&'a A),
+    /// The concatenation of two chunks
+    Concat(&'a SyncChunk<A>, &'a SyncChunk<A>),
+    /// A collection of elements stored contiguously
+    Collect(&'a [A]),
+    /// A pending [`transform`](SyncChunk::transform) or
+    /// [`transform_flatten`](SyncChunk::transform_flatten) of the given chunk
+    TransformFlatten(&'a SyncChunk<A>),
+}
+
+impl<A> Default for SyncChunk<A> {
+    /// Creates a new empty chunk.
+    fn default() -> Self {
+        SyncNode::Empty.into()
+    }
+}
+
+impl<A> SyncChunk<A> {
+    /// Creates a new chunk containing a single element.
+    ///
+    /// # Examples
+    /// ```
+    /// use tailcall_chunk::SyncChunk;
+    ///
+    /// let chunk: SyncChunk<i32> = SyncChunk::new(100);
+    /// assert!(!chunk.is_null());
+    /// ```
+    pub fn new(a: A) -> Self {
+        SyncNode::Single(a).into()
+    }
+
+    /// Returns a read-only view of the root node of the chunk.
+    ///
+    /// Nothing is evaluated, and the view borrows from the chunk.
+    ///
+    /// # Examples
+    /// ```
+    /// use tailcall_chunk::{SyncChunk, SyncChunkNode};
+    ///
+    /// let chunk = SyncChunk::new(1).concat(SyncChunk::new(2).transform(|x| x * 10));
+    /// match chunk.view() {
+    ///     SyncChunkNode::Concat(a, b) => {
+    ///         assert!(matches!(a.view(), SyncChunkNode::Single(1)));
+    ///         assert!(matches!(b.view(), SyncChunkNode::TransformFlatten(_)));
+    ///     }
+    ///     _ => unreachable!(),
+    /// }
+    /// ```
+    pub fn view(&self) -> SyncChunkNode<'_, A> {
+        match &self.node {
+            SyncNode::Empty => SyncChunkNode::Empty,
+            SyncNode::Single(a) => SyncChunkNode::Single(a),
+            SyncNode::Concat(a, b) => SyncChunkNode::Concat(a, b),
+            SyncNode::Collect(vec) => SyncChunkNode::Collect(vec),
+            SyncNode::TransformFlatten(a, _) => SyncChunkNode::TransformFlatten(a),
+        }
+    }
+
+    /// Returns `true` if the chunk is empty.
+    ///
+    /// # Examples
+    /// ```
+    /// use tailcall_chunk::SyncChunk;
+    ///
+    /// let chunk: SyncChunk<i32> = SyncChunk::default();
+    /// assert!(chunk.is_null());
+    /// assert!(!chunk.append(42).is_null());
+    /// ```
+    pub fn is_null(&self) -> bool {
+        match &self.node {
+            SyncNode::Empty => true,
+            SyncNode::Collect(vec) => vec.is_empty(),
+            _ => false,
+        }
+    }
+
+    /// Append a new element to the chunk.
+    ///
+    /// # Examples
+    /// ```
+    /// use tailcall_chunk::SyncChunk;
+    ///
+    /// let chunk = SyncChunk::default().append(1).append(2);
+    /// assert_eq!(chunk.as_vec(), vec![1, 2]);
+    /// ```
+    pub fn append(self, a: A) -> Self {
+        self.concat(SyncChunk::new(a))
+    }
+
+    /// Prepend a new element to the beginning of the chunk.
+    ///
+    /// # Examples
+    /// ```
+    /// use tailcall_chunk::SyncChunk;
+    ///
+    /// let chunk = SyncChunk::default().prepend(1).prepend(2);
+    /// assert_eq!(chunk.as_vec(), vec![2, 1]);
+    /// ```
+    pub fn prepend(self, a: A) -> Self {
+        if self.is_null() {
+            SyncChunk::new(a)
+        } else {
+            SyncChunk::new(a).concat(self)
+        }
+    }
+
+    /// Concatenates this chunk with another chunk.
+    ///
+    /// If either chunk is empty, the other chunk is returned as is. Appending a single element
+    /// to a `Collect` that no other version shares pushes into the vector in place.
+    ///
+    /// # Examples
+    /// ```
+    /// use tailcall_chunk::SyncChunk;
+    ///
+    /// let chunk1 = SyncChunk::default().append(1).append(2);
+    /// let chunk2 = SyncChunk::default().append(3).append(4);
+    /// assert_eq!(chunk1.concat(chunk2).as_vec(), vec![1, 2, 3, 4]);
+    /// ```
+    pub fn concat(self, other: SyncChunk<A>) -> SyncChunk<A> {
+        let node = match (self.node, other.node) {
+            // Handle null cases
+            (SyncNode::Empty, other) => other,
+            (this, SyncNode::Empty) => this,
+            (SyncNode::Single(a), SyncNode::Single(b)) => SyncNode::Collect(Arc::new(vec![a, b])),
+            (SyncNode::Collect(mut vec), SyncNode::Single(a)) => {
+                if let Some(inner) = Arc::get_mut(&mut vec) {
+                    inner.push(a);
+                    SyncNode::Collect(vec)
+                } else {
+                    SyncNode::Concat(
+                        SyncLink::new(SyncNode::Collect(vec).into()),
+                        SyncLink::new(SyncNode::Single(a).into()),
+                    )
+                }
+            }
+            // Handle all other cases with Concat
+            (this, that) => {
+                SyncNode::Concat(SyncLink::new(this.into()), SyncLink::new(that.into()))
+            }
+        };
+        node.into()
+    }
+
+    /// Transforms each element in the chunk using the provided function.
+    ///
+    /// The transformation is lazy and only runs when the chunk is materialized.
+    ///
+    /// # Examples
+    /// ```
+    /// use tailcall_chunk::SyncChunk;
+    ///
+    /// let chunk = SyncChunk::default().append(1).append(2).append(3);
+    /// assert_eq!(chunk.transform(|x| x * 2).as_vec(), vec![2, 4, 6]);
+    /// ```
+    pub fn transform(self, f: impl Fn(A) -> A + Send + Sync + 'static) -> Self {
+        self.transform_flatten(move |a| SyncChunk::new(f(a)))
+    }
+
+    /// Materializes a chunk by converting it into a collected form.
+    ///
+    /// # Examples
+    /// ```
+    /// use tailcall_chunk::SyncChunk;
+    ///
+    /// let chunk = SyncChunk::default().append(1).append(2).transform(|x| x * 2);
+    /// assert_eq!(chunk.materialize().as_vec(), vec![2, 4]);
+    /// ```
+    pub fn materialize(self) -> SyncChunk<A>
+    where
+        A: Clone,
+    {
+        SyncNode::Collect(Arc::new(self.as_vec())).into()
+    }
+
+    /// Transforms each element in the chunk into a new chunk and flattens the result.
+    ///
+    /// # Examples
+    /// ```
+    /// use tailcall_chunk::SyncChunk;
+    ///
+    /// let chunk = SyncChunk::default().append(1).append(2);
+    /// let expanded = chunk.transform_flatten(|x| SyncChunk::default().append(x).append(x + 1));
+    /// assert_eq!(expanded.as_vec(), vec![1, 2, 2, 3]);
+    /// ```
+    pub fn transform_flatten(self, f: impl Fn(A) -> SyncChunk<A> + Send + Sync + 'static) -> Self {
+        SyncNode::TransformFlatten(SyncLink::new(self), Arc::new(f)).into()
+    }
+
+    /// Converts the chunk into a vector of its elements.
+    ///
+    /// # Examples
+    /// ```
+    /// use tailcall_chunk::SyncChunk;
+    ///
+    /// let chunk = SyncChunk::default().append(1).append(2).append(3);
+    /// assert_eq!(chunk.as_vec(), vec![1, 2, 3]);
+    /// ```
+    pub fn as_vec(&self) -> Vec<A>
+    where
+        A: Clone,
+    {
+        let mut vec = Vec::new();
+        self.as_vec_mut(&mut vec);
+        vec
+    }
+
+    /// Helper method that populates a vector with the chunk's elements.
+    ///
+    /// The traversal uses an explicit work stack instead of recursion, so chunks of any depth
+    /// (e.g. built from millions of [`prepend`](SyncChunk::prepend) calls) can be materialized
+    /// without overflowing the call stack.
+    ///
+    /// # Arguments
+    /// * `buf` - A mutable reference to a vector that will be populated with
+    ///   the chunk's elements
+    pub fn as_vec_mut(&self, buf: &mut Vec<A>)
+    where
+        A: Clone,
+    {
+        // Buffers receiving the source elements of the `TransformFlatten` nodes that are
+        // currently being evaluated. Output goes to the innermost one, or to `buf` if none.
+        let mut pending: Vec<Vec<A>> = Vec::new();
+        let mut stack = vec![Task::Borrowed(self)];
+
+        while let Some(task) = stack.pop() {
+            let out = pending.last_mut().unwrap_or(&mut *buf);
+            match task {
+                Task::Borrowed(chunk) => match &chunk.node {
+                    SyncNode::Empty => {}
+                    SyncNode::Single(a) => out.push(a.clone()),
+                    SyncNode::Collect(vec) => out.extend(vec.iter().cloned()),
+                    SyncNode::Concat(a, b) => {
+                        stack.push(Task::Borrowed(b));
+                        stack.push(Task::Borrowed(a));
+                    }
+                    SyncNode::TransformFlatten(a, f) => {
+                        pending.push(Vec::new());
+                        stack.push(Task::Apply(f.clone()));
+                        stack.push(Task::Borrowed(a));
+                    }
+                },
+                Task::Shared(link) => match &link.node {
+                    SyncNode::Empty => {}
+                    SyncNode::Single(a) => out.push(a.clone()),
+                    SyncNode::Collect(vec) => out.extend(vec.iter().cloned()),
+                    SyncNode::Concat(a, b) => {
+                        stack.push(Task::Shared(b.clone()));
+                        stack.push(Task::Shared(a.clone()));
+                    }
+                    SyncNode::TransformFlatten(a, f) => {
+                        pending.push(Vec::new());
+                        stack.push(Task::Apply(f.clone()));
+                        stack.push(Task::Shared(a.clone()));
+                    }
+                },
+                Task::Owned(chunk) => match chunk.node {
+                    SyncNode::Empty => {}
+                    SyncNode::Single(a) => out.push(a),
+                    SyncNode::Collect(vec) => out.extend(vec.iter().cloned()),
+                    SyncNode::Concat(a, b) => {
+                        stack.push(Task::Shared(b));
+                        stack.push(Task::Shared(a));
+                    }
+                    SyncNode::TransformFlatten(a, f) => {
+                        pending.push(Vec::new());
+                        stack.push(Task::Apply(f));
+                        stack.push(Task::Shared(a));
+                    }
+                },
+                Task::Apply(f) => {
+                    // The source of the transformation is fully evaluated at this point
+                    let source = pending.pop().unwrap_or_default();
+                    stack.push(Task::Flatten(f, source.into_iter()));
+                }
+                Task::Flatten(f, mut source) => {
+                    if let Some(elem) = source.next() {
+                        let chunk = f(elem);
+                        stack.push(Task::Flatten(f, source));
+                        stack.push(Task::Owned(chunk));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Moves the child links of this node onto `stack`, dropping the node itself.
+    fn unlink_into(self, stack: &mut Vec<SyncLink<A>>) {
+        match self.node {
+            SyncNode::Concat(a, b) => {
+                stack.push(a);
+                stack.push(b);
+            }
+            SyncNode::TransformFlatten(a, _) => stack.push(a),
+            SyncNode::Empty | SyncNode::Single(_) | SyncNode::Collect(_) => {}
+        }
+    }
+}
+
+/// The function of a `TransformFlatten` node of a [`SyncChunk`].
+pub(crate) type FlattenFn<A> = Arc<dyn Fn(A) -> SyncChunk<A> + Send + Sync>;
+
+/// A unit of work for the iterative traversal in [`SyncChunk::as_vec_mut`].
+enum Task<'a, A> {
+    /// A node borrowed from the chunk being materialized
+    Borrowed(&'a SyncChunk<A>),
+    /// A node shared with a chunk produced during the traversal
+    Shared(SyncLink<A>),
+    /// A chunk returned by a `TransformFlatten` function
+    Owned(SyncChunk<A>),
+    /// Takes the evaluated source of a `TransformFlatten` node off the pending buffers
+    Apply(FlattenFn<A>),
+    /// Feeds the remaining source elements of a `TransformFlatten` node through its function
+    Flatten(FlattenFn<A>, vec::IntoIter<A>),
+}
+
+/// A shared reference to a child of a [`SyncChunk`].
+///
+/// `SyncLink` behaves like an [`Arc<SyncChunk<A>>`](Arc): cloning it is O(1) and the child is
+/// shared between all versions that point to it. In addition, dropping the last reference to
+/// a deep subtree releases its nodes iteratively rather than through recursive drop glue, so
+/// freeing a chunk is stack-safe no matter how many `Concat` or `TransformFlatten` levels it
+/// has.
+pub(crate) struct SyncLink<A>(Arc<SyncChunk<A>>);
+
+impl<A> SyncLink<A> {
+    /// Creates a new link owning the given chunk.
+    pub(crate) fn new(chunk: SyncChunk<A>) -> Self {
+        SyncLink(Arc::new(chunk))
+    }
+
+    /// Takes the chunk out of this link if no other link shares it, leaving an empty chunk
+    /// in its place.
+    fn take_unique(&mut self) -> Option<SyncChunk<A>> {
+        Arc::get_mut(&mut self.0).map(core::mem::take)
+    }
+}
+
+impl<A> Clone for SyncLink<A> {
+    fn clone(&self) -> Self {
+        SyncLink(self.0.clone())
+    }
+}
+
+impl<A> Deref for SyncLink<A> {
+    type Target = SyncChunk<A>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<A> From<SyncChunk<A>> for SyncLink<A> {
+    fn from(chunk: SyncChunk<A>) -> Self {
+        SyncLink::new(chunk)
+    }
+}
+
+impl<A> Drop for SyncLink<A> {
+    /// Releases the linked chunk without recursing into its children.
+    ///
+    /// Every uniquely owned child is unlinked and pushed onto an explicit stack before its
+    /// parent is freed. [`Arc::get_mut`] only succeeds for the last reference, so children
+    /// still shared with other versions, possibly on other threads, are left untouched.
+    fn drop(&mut self) {
+        let mut stack = Vec::new();
+        if let Some(chunk) = self.take_unique() {
+            chunk.unlink_into(&mut stack);
+        }
+        while let Some(mut link) = stack.pop() {
+            if let Some(chunk) = link.take_unique() {
+                chunk.unlink_into(&mut stack);
+            }
+        }
+    }
+}
+
+impl<A> FromIterator<A> for SyncChunk<A> {
+    /// Creates a chunk from an iterator.
+    ///
+    /// # Examples
+    /// ```
+    /// use tailcall_chunk::SyncChunk;
+    ///
+    /// let chunk: SyncChunk<_> = (1..=3).collect();
+    /// assert_eq!(chunk.as_vec(), vec![1, 2, 3]);
+    /// ```
+    fn from_iter<T: IntoIterator<Item = A>>(iter: T) -> Self {
+        let vec: Vec<_> = iter.into_iter().collect();
+
+        SyncNode::Collect(Arc::new(vec)).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn test_send_sync() {
+        assert_send_sync::<SyncChunk<i32>>();
+        assert_send_sync::<SyncChunk<String>>();
+    }
+
+    #[test]
+    fn test_append_prepend_concat() {
+        let chunk = SyncChunk::default().append(2).append(3).prepend(1);
+        assert_eq!(chunk.as_vec(), vec![1, 2, 3]);
+
+        let combined = chunk.clone().concat(SyncChunk::default().append(4));
+        assert_eq!(combined.as_vec(), vec![1, 2, 3, 4]);
+        assert_eq!(chunk.as_vec(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_structural_sharing() {
+        let chunk1 = SyncChunk::default().append(1).append(2);
+        let chunk2 = chunk1.clone().append(3);
+        let chunk3 = chunk1.clone().append(4);
+
+        assert_eq!(chunk1.as_vec(), vec![1, 2]);
+        assert_eq!(chunk2.as_vec(), vec![1, 2, 3]);
+        assert_eq!(chunk3.as_vec(), vec![1, 2, 4]);
+    }
+
+    #[test]
+    fn test_concat_optimization() {
+        let collected: SyncChunk<i32> = vec![1, 2, 3].into_iter().collect();
+        let result = collected.concat(SyncChunk::new(4));
+
+        assert_eq!(result.as_vec(), vec![1, 2, 3, 4]);
+        match result.view() {
+            SyncChunkNode::Collect(_) => (),
+            _ => panic!("Expected Collect variant after optimization"),
+        }
+    }
+
+    #[test]
+    fn test_transform() {
+        let chunk = SyncChunk::default()
+            .append(1)
+            .append(2)
+            .transform(|x| x * 2)
+            .transform_flatten(|x| SyncChunk::default().append(x).append(x + 1));
+        assert_eq!(chunk.as_vec(), vec![2, 3, 4, 5]);
+        assert_eq!(chunk.materialize().as_vec(), vec![2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_as_vec_deep_chunks() {
+        const DEPTH: usize = 1_000_000;
+
+        // A right-leaning chain of a million `Concat` nodes
+        let mut prepended = SyncChunk::default();
+        for i in (0..DEPTH).rev() {
+            prepended = prepended.prepend(i);
+        }
+        let vec = prepended.as_vec();
+        assert_eq!(vec.len(), DEPTH);
+        assert!(vec.iter().enumerate().all(|(i, a)| *a == i));
+
+        // A million nested transformations
+        let mut transformed = SyncChunk::new(0);
+        for _ in 0..DEPTH {
+            transformed = transformed.transform(|x| x + 1);
+        }
+        assert_eq!(transformed.as_vec(), vec![DEPTH]);
+    }
+
+    #[test]
+    fn test_drop_deep_chunks() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        #[derive(Clone)]
+        struct Counted<'a>(&'a AtomicUsize);
+        impl Drop for Counted<'_> {
+            fn drop(&mut self) {
+                self.0.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        const DEPTH: usize = 1_000_000;
+        let dropped = AtomicUsize::new(0);
+
+        let mut chunk = SyncChunk::default();
+        for _ in 0..DEPTH {
+            chunk = chunk.prepend(Counted(&dropped));
+        }
+        let shared = chunk.clone().prepend(Counted(&dropped));
+
+        // Dropping one version keeps the nodes shared with the other alive
+        drop(chunk);
+        assert_eq!(dropped.load(Ordering::Relaxed), 0);
+
+        drop(shared);
+        assert_eq!(dropped.load(Ordering::Relaxed), DEPTH + 1);
+
+        // Deeply nested transformations are released iteratively as well
+        let mut transformed = SyncChunk::new(0);
+        for _ in 0..DEPTH {
+            transformed = transformed.transform(|x| x + 1);
+        }
+        drop(transformed);
+    }
+
+    #[test]
+    fn test_across_threads() {
+        let base: SyncChunk<_> = (0..100).collect();
+        let handles: Vec<_> = (0..4)
+            .map(|i| {
+                let chunk = base.clone();
+                std::thread::spawn(move || chunk.transform(move |x| x + i).as_vec())
+            })
+            .collect();
+
+        for (i, handle) in handles.into_iter().enumerate() {
+            let expected: Vec<_> = (0..100).map(|x| x + i as i32).collect();
+            assert_eq!(handle.join().unwrap(), expected);
+        }
+    }
+}
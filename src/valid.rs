@@ -0,0 +1,317 @@
+//! Error accumulation on top of [`Chunk`].
+//!
+//! A [`Validation`] is either a value or a non-empty [`Chunk`] of errors. Unlike
+//! [`Result`], combining two failed validations keeps the errors of both, and since errors
+//! are joined with [`Chunk::concat`], accumulating them is O(1) no matter how many there are.
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::Chunk;
+
+/// The outcome of a validation: either a value, or every error found while computing it.
+///
+/// A validation created through the methods of this type never holds an empty chunk of
+/// errors. Since the variants are public, one can still be built by hand, e.g. from a
+/// filtered chunk; collecting validations treats it like a successful one.
+///
+/// # Examples
+/// ```
+/// use tailcall_chunk::Validation;
+///
+/// fn check_port(port: i32) -> Validation<String, u16> {
+///     u16::try_from(port)
+///         .map_err(|_| format!("invalid port {port}"))
+///         .into()
+/// }
+///
+/// let ports: Validation<_, Vec<_>> = [80, -1, 443, 70000].into_iter().map(check_port).collect();
+/// assert_eq!(
+///     ports.into_result(),
+///     Err(vec!["invalid port -1".to_string(), "invalid port 70000".to_string()])
+/// );
+/// ```
+#[derive(Clone)]
+pub enum Validation<E, A> {
+    /// A successful validation
+    Valid(A),
+    /// A failed validation, along with its errors in the order they were found
+    Invalid(Chunk<E>),
+}
+
+impl<E, A> Validation<E, A> {
+    /// Creates a failed validation with a single error.
+    ///
+    /// # Examples
+    /// ```
+    /// use tailcall_chunk::Validation;
+    ///
+    /// let invalid = Validation::<_, i32>::fail("missing");
+    /// assert!(!invalid.is_valid());
+    /// ```
+    pub fn fail(e: E) -> Self {
+        Validation::Invalid(Chunk::new(e))
+    }
+
+    /// Returns `true` if the validation succeeded.
+    pub fn is_valid(&self) -> bool {
+        matches!(self, Validation::Valid(_))
+    }
+
+    /// Transforms the value of a successful validation, leaving errors untouched.
+    ///
+    /// # Examples
+    /// ```
+    /// use tailcall_chunk::Validation;
+    ///
+    /// let valid = Validation::<(), _>::Valid(2).map(|x| x * 10);
+    /// assert_eq!(valid, Validation::Valid(20));
+    /// ```
+    pub fn map<B>(self, f: impl FnOnce(A) -> B) -> Validation<E, B> {
+        match self {
+            Validation::Valid(a) => Validation::Valid(f(a)),
+            Validation::Invalid(errors) => Validation::Invalid(errors),
+        }
+    }
+
+    /// Continues with another validation that depends on the value of this one.
+    ///
+    /// Since `f` needs the value, it doesn't run if this validation failed, and only the
+    /// errors found so far are returned. Use [`zip`](Validation::zip) or
+    /// [`and`](Validation::and) to accumulate the errors of independent validations.
+    ///
+    /// # Examples
+    /// ```
+    /// use tailcall_chunk::Validation;
+    ///
+    /// let positive = |x: i32| match x {
+    ///     1.. => Validation::Valid(x),
+    ///     _ => Validation::fail("not positive"),
+    /// };
+    /// assert_eq!(Validation::Valid(1).and_then(positive), Validation::Valid(1));
+    /// assert_eq!(Validation::Valid(0).and_then(positive), Validation::fail("not positive"));
+    /// ```
+    pub fn and_then<B>(self, f: impl FnOnce(A) -> Validation<E, B>) -> Validation<E, B> {
+        match self {
+            Validation::Valid(a) => f(a),
+            Validation::Invalid(errors) => Validation::Invalid(errors),
+        }
+    }
+
+    /// Combines two independent validations into a validation of both values.
+    ///
+    /// If either fails, the result holds the errors of both sides, those of `self` first.
+    ///
+    /// # Performance
+    /// Joining the errors is O(1).
+    ///
+    /// # Examples
+    /// ```
+    /// use tailcall_chunk::Validation;
+    ///
+    /// let both = Validation::<&str, _>::Valid(1).zip(Validation::Valid("a"));
+    /// assert_eq!(both, Validation::Valid((1, "a")));
+    ///
+    /// let errors = Validation::<_, i32>::fail("a").zip(Validation::<_, i32>::fail("b"));
+    /// assert_eq!(errors.into_result(), Err(vec!["a", "b"]));
+    /// ```
+    pub fn zip<B>(self, other: Validation<E, B>) -> Validation<E, (A, B)> {
+        match (self, other) {
+            (Validation::Valid(a), Validation::Valid(b)) => Validation::Valid((a, b)),
+            (Validation::Valid(_), Validation::Invalid(errors))
+            | (Validation::Invalid(errors), Validation::Valid(_)) => Validation::Invalid(errors),
+            (Validation::Invalid(a), Validation::Invalid(b)) => Validation::Invalid(a.concat(b)),
+        }
+    }
+
+    /// Combines two independent validations, keeping the value of `other`.
+    ///
+    /// Like [`zip`](Validation::zip), the errors of both sides are accumulated.
+    ///
+    /// # Examples
+    /// ```
+    /// use tailcall_chunk::Validation;
+    ///
+    /// let checked = Validation::<&str, ()>::Valid(()).and(Validation::Valid(1));
+    /// assert_eq!(checked, Validation::Valid(1));
+    /// ```
+    pub fn and<B>(self, other: Validation<E, B>) -> Validation<E, B> {
+        self.zip(other).map(|(_, b)| b)
+    }
+
+    /// Converts the validation into a `Result`, with the errors in the order they were found.
+    ///
+    /// # Examples
+    /// ```
+    /// use tailcall_chunk::Validation;
+    ///
+    /// assert_eq!(Validation::<&str, _>::Valid(1).into_result(), Ok(1));
+    /// assert_eq!(Validation::<_, i32>::fail("a").into_result(), Err(vec!["a"]));
+    /// ```
+    pub fn into_result(self) -> Result<A, Vec<E>>
+    where
+        E: Clone,
+    {
+        match self {
+            Validation::Valid(a) => Ok(a),
+            Validation::Invalid(errors) => Err(errors.into_vec()),
+        }
+    }
+}
+
+impl<E: PartialEq + Clone, A: PartialEq> PartialEq for Validation<E, A> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Validation::Valid(a), Validation::Valid(b)) => a == b,
+            (Validation::Invalid(a), Validation::Invalid(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl<E: Eq + Clone, A: Eq> Eq for Validation<E, A> {}
+
+impl<E: fmt::Debug + Clone, A: fmt::Debug> fmt::Debug for Validation<E, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Validation::Valid(a) => f.debug_tuple("Valid").field(a).finish(),
+            Validation::Invalid(errors) => f.debug_tuple("Invalid").field(errors).finish(),
+        }
+    }
+}
+
+impl<E, A> From<Result<A, E>> for Validation<E, A> {
+    /// Converts a `Result` into a validation with at most one error.
+    fn from(result: Result<A, E>) -> Self {
+        match result {
+            Ok(a) => Validation::Valid(a),
+            Err(e) => Validation::fail(e),
+        }
+    }
+}
+
+impl<E: Clone, A> From<Validation<E, A>> for Result<A, Vec<E>> {
+    /// See [`Validation::into_result`].
+    fn from(validation: Validation<E, A>) -> Self {
+        validation.into_result()
+    }
+}
+
+impl<E: Clone, A, C: FromIterator<A>> FromIterator<Validation<E, A>> for Validation<E, C> {
+    /// Collects the values of all validations, or the errors of every failed one.
+    ///
+    /// Unlike collecting into a `Result`, this doesn't stop at the first failure. Mapping
+    /// a validating function over an iterator and collecting the results traverses it.
+    /// The result only fails if there is at least one error, which may evaluate pending
+    /// transformations of the errors up to the first one.
+    ///
+    /// # Examples
+    /// ```
+    /// use tailcall_chunk::Validation;
+    ///
+    /// let all: Validation<&str, Vec<_>> = vec![Validation::Valid(1), Validation::Valid(2)]
+    ///     .into_iter()
+    ///     .collect();
+    /// assert_eq!(all, Validation::Valid(vec![1, 2]));
+    ///
+    /// let some: Validation<_, Vec<i32>> =
+    ///     vec![Validation::fail("a"), Validation::Valid(2), Validation::fail("b")]
+    ///         .into_iter()
+    ///         .collect();
+    /// assert_eq!(some.into_result(), Err(vec!["a", "b"]));
+    /// ```
+    fn from_iter<T: IntoIterator<Item = Validation<E, A>>>(iter: T) -> Self {
+        let mut values = Vec::new();
+        let mut errors = Chunk::default();
+        for validation in iter {
+            match validation {
+                Validation::Valid(a) => values.push(a),
+                Validation::Invalid(e) => errors = errors.concat(e),
+            }
+        }
+        if errors.is_empty() {
+            Validation::Valid(values.into_iter().collect())
+        } else {
+            Validation::Invalid(errors)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn positive(x: i32) -> Validation<String, i32> {
+        if x > 0 {
+            Validation::Valid(x)
+        } else {
+            Validation::fail(format!("{x} is not positive"))
+        }
+    }
+
+    #[test]
+    fn test_zip_accumulates_errors() {
+        let valid = positive(1).zip(positive(2)).zip(positive(3));
+        assert_eq!(valid, Validation::Valid(((1, 2), 3)));
+
+        let invalid = positive(0).zip(positive(2)).and(positive(-1));
+        assert_eq!(
+            invalid.into_result(),
+            Err(vec![
+                "0 is not positive".to_string(),
+                "-1 is not positive".to_string()
+            ])
+        );
+    }
+
+    #[test]
+    fn test_and_then_short_circuits() {
+        let calls = std::cell::Cell::new(0);
+        let chained = positive(0).and_then(|x| {
+            calls.set(calls.get() + 1);
+            positive(x - 1)
+        });
+        assert_eq!(chained.into_result().unwrap_err().len(), 1);
+        assert_eq!(calls.get(), 0);
+
+        assert_eq!(
+            positive(2).and_then(|x| positive(x - 1)).map(|x| x * 10),
+            Validation::Valid(10)
+        );
+    }
+
+    #[test]
+    fn test_traverse() {
+        let all: Validation<_, Vec<_>> = (1..=3).map(positive).collect();
+        assert_eq!(all, Validation::Valid(vec![1, 2, 3]));
+
+        let errors: Validation<_, Vec<_>> = (-1..=1).map(positive).collect();
+        assert_eq!(
+            Result::from(errors),
+            Err(vec![
+                "-1 is not positive".to_string(),
+                "0 is not positive".to_string()
+            ])
+        );
+
+        let empty: Validation<String, Chunk<i32>> = core::iter::empty().collect();
+        assert_eq!(empty, Validation::Valid(Chunk::default()));
+
+        // Failures built by hand without any error don't fail the result
+        let errors = Chunk::new("hidden".to_string()).transform_flatten(|_| Chunk::default());
+        let filtered: Validation<_, Vec<_>> = [
+            Validation::Valid(1),
+            Validation::Invalid(errors),
+            Validation::Invalid(Chunk::default()),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(filtered, Validation::Valid(vec![1]));
+    }
+
+    #[test]
+    fn test_accumulates_many_errors() {
+        let errors: Validation<_, Vec<_>> = (0..100_000).map(|_| positive(0)).collect();
+        assert_eq!(errors.into_result().unwrap_err().len(), 100_000);
+    }
+}